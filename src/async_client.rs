@@ -0,0 +1,189 @@
+use reqwest::{Url, Response};
+use reqwest::header::HeaderMap;
+use serde::de::DeserializeOwned;
+
+use crate::types::*;
+use crate::error::*;
+use crate::endpoint;
+use crate::graphql::{self, TxPage, Filter};
+
+async fn fail<T>(rsp: Response) -> Result<T, Error> {
+    let (status, url, headers) = (rsp.status().as_u16(), rsp.url().to_string(), Box::new(rsp.headers().clone()));
+    Err(Error::HttpStatus { status, url, headers, body: rsp.text().await? })
+}
+
+async fn check_status(rsp: Response) -> Result<Response, Error> {
+    if rsp.status().is_success() {
+        Ok(rsp)
+    } else {
+        fail(rsp).await
+    }
+}
+
+async fn json<T: DeserializeOwned>(rsp: Response) -> Result<T, Error> {
+    Ok(serde_json::from_str(&rsp.text().await?)?)
+}
+
+pub struct AsyncClient {
+    http: reqwest::Client,
+    url: Url,
+}
+
+impl AsyncClient {
+    pub fn new() -> Result<AsyncClient, Error> {
+        AsyncClient::with_url(endpoint::default_target())
+    }
+
+    pub fn with_url<U: AsRef<str>>(url: U) -> Result<AsyncClient, Error> {
+        Ok(AsyncClient { http: reqwest::Client::new(), url: endpoint::parse(url)? })
+    }
+
+    pub fn with_headers(self, headers: HeaderMap) -> Result<Self, Error> {
+        let http = reqwest::Client::builder().default_headers(headers).build()?;
+        Ok(AsyncClient { http, ..self })
+    }
+
+    async fn get(&self, path: &str) -> Result<Response, Error> {
+        Ok(self.http.get(self.url.join(path)?).send().await?)
+    }
+
+    async fn get_ok(&self, path: &str) -> Result<Response, Error> {
+        check_status(self.get(path).await?).await
+    }
+
+    pub async fn get_raw(&self, path: &str) -> Result<String, Error> {
+        Ok(self.get_ok(path).await?.text().await?)
+    }
+
+    pub async fn info(&self) -> Result<Info, Error> {
+        json(self.get_ok(&endpoint::info()).await?).await
+    }
+
+    pub async fn peers(&self) -> Result<Vec<String>, Error> {
+        json(self.get_ok(&endpoint::peers()).await?).await
+    }
+
+    pub async fn block<T: AsRef<BlockHash>>(&self, t: T) -> Result<Block, Error> {
+        json(self.get_ok(&endpoint::block(t.as_ref())).await?).await
+    }
+
+    pub async fn height<T: AsRef<Height>>(&self, t: T) -> Result<Block, Error> {
+        json(self.get_ok(&endpoint::height(t.as_ref())).await?).await
+    }
+
+    pub async fn current_block(&self) -> Result<Block, Error> {
+        json(self.get_ok(&endpoint::current_block()).await?).await
+    }
+
+    pub async fn block_index(&self) -> Result<Vec<BlockIndexEntry>, Error> {
+        json(self.get_ok(&endpoint::block_index()).await?).await
+    }
+
+    pub async fn hash_at_height(&self, h: Height) -> Result<BlockHash, Error> {
+        let s = self.get_raw(&endpoint::height_field(&h, "indep_hash")).await?;
+        BlockHash::decode(s.trim().trim_matches('"'))
+    }
+
+    pub async fn tx<T: AsRef<TxHash>>(&self, t: T) -> Result<Tx, Error> {
+        json(self.get_ok(&endpoint::tx(t.as_ref())).await?).await
+    }
+
+    pub async fn tx_field<T: AsRef<TxHash>>(&self, t: T, field: &str) -> Result<String, Error> {
+        Ok(self.get_ok(&endpoint::tx_field(t.as_ref(), field)).await?.text().await?)
+    }
+
+    pub async fn tx_quantity<T: AsRef<TxHash>>(&self, t: T) -> Result<Winstons, Error> {
+        Winstons::decode(self.tx_field(t, "quantity").await?)
+    }
+
+    pub async fn tx_reward<T: AsRef<TxHash>>(&self, t: T) -> Result<Winstons, Error> {
+        Winstons::decode(self.tx_field(t, "reward").await?)
+    }
+
+    pub async fn data<T: AsRef<TxHash>>(&self, t: T) -> Result<Vec<u8>, Error> {
+        let rsp = self.get(&endpoint::data(t.as_ref())).await?;
+        match rsp.status().as_u16() {
+            200 => Ok(rsp.bytes().await?.to_vec()),
+            202 | 404 => Err(Error::value_not_present("data", &format!("transaction {}", t.as_ref()))),
+            _ => fail(rsp).await,
+        }
+    }
+
+    pub async fn resolve(&self, ar_url: &str) -> Result<Vec<u8>, Error> {
+        self.data(endpoint::parse_ar_url(ar_url)?).await
+    }
+
+    pub async fn tx_offset<T: AsRef<TxHash>>(&self, t: T) -> Result<TxOffset, Error> {
+        let rsp = self.get(&endpoint::tx_field(t.as_ref(), "offset")).await?;
+        match rsp.status().as_u16() {
+            200 => json(rsp).await,
+            404 => Err(Error::value_not_present("offset", &format!("transaction {}", t.as_ref()))),
+            _ => fail(rsp).await,
+        }
+    }
+
+    pub async fn tx_status<T: AsRef<TxHash>>(&self, t: T) -> Result<TxStatus, Error> {
+        let rsp = self.get(&endpoint::tx_status(t.as_ref())).await?;
+        match rsp.status().as_u16() {
+            200 => Ok(TxStatus::from(json::<Confirmation>(rsp).await?)),
+            202 => Ok(TxStatus::Pending),
+            404 => Ok(TxStatus::NotFound),
+            _ => fail(rsp).await,
+        }
+    }
+
+    pub async fn pending(&self) -> Result<Vec<TxHash>, Error> {
+        json(self.get_ok(&endpoint::pending()).await?).await
+    }
+
+    pub async fn tx_anchor(&self) -> Result<Anchor, Error> {
+        Anchor::decode(self.get_ok(&endpoint::tx_anchor()).await?.text().await?)
+    }
+
+    pub async fn submit<T: AsRef<Tx>>(&self, t: T) -> Result<TxHash, Error> {
+        let url = self.url.join(&endpoint::submit())?;
+        check_status(self.http.post(url).json(t.as_ref()).send().await?).await?;
+        Ok(t.as_ref().id.clone())
+    }
+
+    pub async fn post_chunk<T: AsRef<Chunk>>(&self, t: T) -> Result<(), Error> {
+        let url = self.url.join(&endpoint::chunk())?;
+        check_status(self.http.post(url).json(t.as_ref()).send().await?).await?;
+        Ok(())
+    }
+
+    pub async fn graphql(&self, query: &str, variables: serde_json::Value) -> Result<serde_json::Value, Error> {
+        let url = self.url.join(&endpoint::graphql())?;
+        let body = serde_json::json!({ "query": query, "variables": variables });
+        graphql::data(json(check_status(self.http.post(url).json(&body).send().await?).await?).await?)
+    }
+
+    async fn transactions(&self, filter: &Filter<'_>, after: Option<String>) -> Result<TxPage, Error> {
+        graphql::page(self.graphql(graphql::TRANSACTIONS, filter.variables(after)).await?)
+    }
+
+    pub async fn query_by_tags(&self, tags: &[(&str, &str)], after: Option<String>) -> Result<TxPage, Error> {
+        self.transactions(&Filter::tags(tags), after).await
+    }
+
+    pub async fn query_by_owner(&self, a: &Address, after: Option<String>) -> Result<TxPage, Error> {
+        self.transactions(&Filter::owner(a), after).await
+    }
+
+    pub async fn query_by_recipient(&self, a: &Address, after: Option<String>) -> Result<TxPage, Error> {
+        self.transactions(&Filter::recipient(a), after).await
+    }
+
+    pub async fn balance<T: AsRef<Address>>(&self, t: T) -> Result<Winstons, Error> {
+        Winstons::decode(self.get_ok(&endpoint::balance(t.as_ref())).await?.text().await?)
+    }
+
+    pub async fn last_tx<T: AsRef<Address>>(&self, t: T) -> Result<Option<TxHash>, Error> {
+        TxHash::decode_optional(self.get_ok(&endpoint::last_tx(t.as_ref())).await?.text().await?)
+    }
+
+    pub async fn price<T: AsRef<Address>>(&self, t: Option<T>, size: usize) -> Result<Winstons, Error> {
+        let path = endpoint::price(t.as_ref().map(AsRef::as_ref), size);
+        Winstons::decode(self.get_ok(&path).await?.text().await?)
+    }
+}
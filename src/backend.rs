@@ -0,0 +1,24 @@
+use reqwest::Url;
+
+use crate::error::Error;
+
+/// Only the status and body are returned, so HttpStatus errors from a custom backend carry no headers.
+pub trait HttpBackend: Send + Sync {
+    fn get(&self, url: &Url) -> Result<(u16, Vec<u8>), Error>;
+    fn post(&self, url: &Url, body: &[u8]) -> Result<(u16, Vec<u8>), Error>;
+}
+
+#[cfg(feature = "blocking")]
+impl HttpBackend for reqwest::blocking::Client {
+    fn get(&self, url: &Url) -> Result<(u16, Vec<u8>), Error> {
+        let rsp = reqwest::blocking::Client::get(self, url.clone()).send()?;
+        Ok((rsp.status().as_u16(), rsp.bytes()?.to_vec()))
+    }
+
+    fn post(&self, url: &Url, body: &[u8]) -> Result<(u16, Vec<u8>), Error> {
+        let rsp = reqwest::blocking::Client::post(self, url.clone())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.to_vec()).send()?;
+        Ok((rsp.status().as_u16(), rsp.bytes()?.to_vec()))
+    }
+}
@@ -1,55 +1,638 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io::Read;
+use std::time::{Duration, Instant};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
 use reqwest::Url;
+use reqwest::header::HeaderMap;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
 
 use crate::types::*;
 use crate::error::*;
+use crate::endpoint;
+use crate::gateway::Gateway;
+use crate::backend::HttpBackend;
+use crate::tx_builder::TxBuilder;
+use crate::graphql::{self, TxPage, Filter};
+use crate::merkle::{DataTree, MAX_CHUNK_SIZE};
 
-pub struct Client {
+const MAX_CONCURRENT_REQUESTS: usize = 8;
+
+struct Backoff {
+    max: u32,
+    base_delay: Duration,
+}
+
+impl Backoff {
+    fn delay(&self, attempt: u32) -> Duration {
+        let exp = self.base_delay * 2u32.saturating_pow(attempt);
+        exp + self.base_delay.mul_f64(rand::random::<f64>())
+    }
+}
+
+// the whole target rather than whether there is one: transfers to a new wallet carry an extra fee
+type PriceKey = (usize, Option<String>);
+
+struct PriceCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<PriceKey, (Instant, Winstons)>>,
+}
+
+struct Response {
+    status: u16,
     url: Url,
+    headers: HeaderMap,
+    len: Option<u64>,
+    body: Box<dyn Read + Send>,
+}
+
+impl Response {
+    fn buffered(url: &Url, (status, body): (u16, Vec<u8>)) -> Self {
+        Response {
+            status,
+            url: url.clone(),
+            headers: HeaderMap::new(),
+            len: Some(body.len() as u64),
+            body: Box::new(std::io::Cursor::new(body)),
+        }
+    }
+
+    fn fail<T>(self) -> Result<T, Error> {
+        let (status, url, headers) = (self.status, self.url.to_string(), Box::new(self.headers.clone()));
+        Err(Error::HttpStatus { status, url, headers, body: self.text()? })
+    }
+
+    fn is_success(&self) -> bool { (200..300).contains(&self.status) }
+
+    fn bytes(mut self) -> Result<Vec<u8>, Error> {
+        let mut bs = Vec::new();
+        self.body.read_to_end(&mut bs)?;
+        Ok(bs)
+    }
+
+    fn text(self) -> Result<String, Error> {
+        Ok(String::from_utf8_lossy(&self.bytes()?).into_owned())
+    }
+}
+
+impl From<reqwest::blocking::Response> for Response {
+    fn from(rsp: reqwest::blocking::Response) -> Self {
+        Response {
+            status: rsp.status().as_u16(),
+            url: rsp.url().clone(),
+            headers: rsp.headers().clone(),
+            len: rsp.content_length(),
+            body: Box::new(rsp),
+        }
+    }
+}
+
+type ProgressFn = Arc<dyn Fn(u64, u64) + Send + Sync>;
+
+struct Progress<R> {
+    inner: R,
+    done: u64,
+    total: u64,
+    f: ProgressFn,
+}
+
+impl<R: Read> Read for Progress<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        if n > 0 {
+            self.done += n as u64;
+            (self.f)(self.done, self.total);
+        }
+        Ok(n)
+    }
+}
+
+fn is_transient(r: &Result<Response, Error>) -> bool {
+    match r {
+        Ok(rsp) => rsp.status == 429 || (500..600).contains(&rsp.status),
+        Err(Error::ReqwestError(e)) => e.is_connect() || e.is_timeout(),
+        Err(Error::IoError(e)) => matches!(e.kind(),
+            std::io::ErrorKind::ConnectionRefused | std::io::ErrorKind::ConnectionReset
+                | std::io::ErrorKind::TimedOut),
+        Err(_) => false,
+    }
+}
+
+struct BlocksBack<'a> {
+    client: &'a Client,
+    next: Option<BlockHash>,
+}
+
+impl Iterator for BlocksBack<'_> {
+    type Item = Result<Block, Error>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let bh = self.next.take()?;
+        let r = self.client.block(&bh);
+        if let Ok(b) = &r {
+            self.next = b.previous_block().cloned();
+        }
+        Some(r)
+    }
+}
+
+struct WalletTxs<'a> {
+    client: &'a Client,
+    filters: Vec<Filter<'static>>,
+    next_page: Option<(Filter<'static>, String)>,
+    buffer: VecDeque<TxHash>,
+    seen: HashSet<TxHash>,
+}
+
+impl Iterator for WalletTxs<'_> {
+    type Item = Result<TxHash, Error>;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            while let Some(txh) = self.buffer.pop_front() {
+                if self.seen.insert(txh.clone()) {
+                    return Some(Ok(txh))
+                }
+            }
+
+            let (filter, after) = match self.next_page.take() {
+                Some((f, c)) => (f, Some(c)),
+                None => (self.filters.pop()?, None),
+            };
+            match self.client.transactions(&filter, after) {
+                Ok(p) => {
+                    self.buffer.extend(p.txs);
+                    self.next_page = p.cursor.map(|c| (filter, c));
+                },
+                Err(e) => {
+                    self.filters.clear();
+                    return Some(Err(e))
+                },
+            }
+        }
+    }
+}
+
+fn check_status(rsp: Response) -> Result<Response, Error> {
+    if rsp.is_success() {
+        Ok(rsp)
+    } else {
+        rsp.fail()
+    }
+}
+
+fn json<T: DeserializeOwned>(rsp: Response) -> Result<T, Error> {
+    Ok(serde_json::from_slice(&rsp.bytes()?)?)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedirectPolicy {
+    None,
+    Limited(usize),
+}
+
+impl Default for RedirectPolicy {
+    fn default() -> Self { RedirectPolicy::Limited(10) }
+}
+
+impl From<RedirectPolicy> for reqwest::redirect::Policy {
+    fn from(p: RedirectPolicy) -> Self {
+        match p {
+            RedirectPolicy::None => reqwest::redirect::Policy::none(),
+            RedirectPolicy::Limited(n) => reqwest::redirect::Policy::limited(n),
+        }
+    }
+}
+
+pub struct Client {
+    http: reqwest::blocking::Client,
+    backend: Option<Box<dyn HttpBackend>>,
+    gateways: Vec<Url>,
+    backoff: Option<Backoff>,
+    network: Option<String>,
+    redirect: RedirectPolicy,
+    headers: HeaderMap,
+    price_cache: Option<PriceCache>,
+    progress: Option<ProgressFn>,
 }
 
 impl Client {
     pub fn new() -> Result<Client, Error> {
-        let url = Url::parse(&std::env::var("ARWEAVE_TARGET")
-                             .unwrap_or("https://arweave.net".to_string()))?;
-        Ok(Client { url })
+        Client::with_url(endpoint::default_target())
+    }
+
+    pub fn with_url<U: AsRef<str>>(url: U) -> Result<Client, Error> {
+        Client::with_gateways(vec![endpoint::parse(url)?])
+    }
+
+    pub fn with_gateways(gateways: Vec<Url>) -> Result<Client, Error> {
+        if gateways.is_empty() {
+            Err(Error::invalid_value("gateways", "at least one gateway is required"))
+        } else {
+            let c = Client {
+                http: reqwest::blocking::Client::new(),
+                backend: None,
+                gateways: gateways.into_iter().map(endpoint::base).collect(),
+                backoff: None,
+                network: None,
+                redirect: RedirectPolicy::default(),
+                headers: HeaderMap::new(),
+                price_cache: None,
+                progress: None,
+            };
+            Ok(Client { http: c.build_http()?, ..c })
+        }
+    }
+
+    fn build_http(&self) -> Result<reqwest::blocking::Client, Error> {
+        Ok(reqwest::blocking::Client::builder()
+            .redirect(self.redirect.into())
+            .default_headers(self.headers.clone())
+            .build()?)
+    }
+
+    pub fn with_redirect_policy(self, redirect: RedirectPolicy) -> Result<Self, Error> {
+        let c = Client { redirect, ..self };
+        Ok(Client { http: c.build_http()?, ..c })
+    }
+
+    pub fn with_headers(self, headers: HeaderMap) -> Result<Self, Error> {
+        let c = Client { headers, ..self };
+        Ok(Client { http: c.build_http()?, ..c })
+    }
+
+    pub fn with_backend<B: HttpBackend + 'static>(self, backend: B) -> Self {
+        Client { backend: Some(Box::new(backend)), ..self }
+    }
+
+    /// The callback receives the bytes transferred so far and the total, or 0 when the gateway doesn't say.
+    pub fn with_progress<F: Fn(u64, u64) + Send + Sync + 'static>(self, f: F) -> Self {
+        Client { progress: Some(Arc::new(f)), ..self }
+    }
+
+    pub fn with_price_cache(self, ttl: Duration) -> Self {
+        Client { price_cache: Some(PriceCache { ttl, entries: Mutex::new(HashMap::new()) }), ..self }
+    }
+
+    pub fn with_retries(self, max: u32, base_delay: Duration) -> Self {
+        Client { backoff: Some(Backoff { max, base_delay }), ..self }
+    }
+
+    pub fn expect_network(&mut self, name: &str) {
+        self.network = Some(name.to_string());
+    }
+
+    fn check_network(&self) -> Result<(), Error> {
+        match &self.network {
+            Some(n) => {
+                let i = self.info()?;
+                match i.network {
+                    Some(ref m) if m == n => Ok(()),
+                    actual => Err(Error::NetworkMismatch { expected: n.clone(), actual }),
+                }
+            },
+            None => Ok(()),
+        }
+    }
+
+    fn retrying<F>(&self, f: F) -> Result<Response, Error>
+    where F: Fn() -> Result<Response, Error> {
+        let mut attempt = 0;
+        loop {
+            let r = f();
+            match &self.backoff {
+                Some(b) if attempt < b.max && is_transient(&r) => {
+                    std::thread::sleep(b.delay(attempt));
+                    attempt += 1;
+                },
+                _ => return r,
+            }
+        }
+    }
+
+    fn send<F>(&self, path: &str, f: F) -> Result<Response, Error>
+    where F: Fn(Url) -> Result<Response, Error> {
+        let mut r = None;
+        for g in self.gateways.iter() {
+            let rsp = f(g.join(path)?);
+            let failover = is_transient(&rsp);
+            r = Some(rsp);
+            if !failover { break }
+        }
+        r.expect("at least one gateway")
+    }
+
+    fn get_once(&self, url: &Url) -> Result<Response, Error> {
+        match &self.backend {
+            Some(b) => b.get(url).map(|r| Response::buffered(url, r)),
+            None => Ok(Response::from(self.http.get(url.clone()).send()?)),
+        }
+    }
+
+    fn post_once(&self, url: &Url, body: &[u8]) -> Result<Response, Error> {
+        match &self.backend {
+            Some(b) => b.post(url, body).map(|r| Response::buffered(url, r)),
+            None => Ok(Response::from(self.http.post(url.clone())
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body.to_vec()).send()?)),
+        }
+    }
+
+    fn get(&self, path: &str) -> Result<Response, Error> {
+        self.send(path, |url| self.retrying(|| self.get_once(&url)))
+    }
+
+    fn post<T: Serialize>(&self, path: &str, t: &T) -> Result<Response, Error> {
+        let body = serde_json::to_vec(t)?;
+        check_status(self.send(path, |url| self.post_once(&url, &body))?)
+    }
+
+    fn get_ok(&self, path: &str) -> Result<Response, Error> {
+        check_status(self.get(path)?)
+    }
+
+    pub fn get_raw(&self, path: &str) -> Result<String, Error> {
+        self.get_ok(path)?.text()
     }
 
     pub fn info(&self) -> Result<Info, Error> {
-        Ok(reqwest::get(self.url.join("info")?)?.json()?)
+        json(self.get_ok(&endpoint::info())?)
+    }
+
+    pub fn peers(&self) -> Result<Vec<String>, Error> {
+        json(self.get_ok(&endpoint::peers())?)
     }
 
     pub fn block<T: AsRef<BlockHash>>(&self, t: T) -> Result<Block, Error> {
-        Ok(reqwest::get(self.url.join("block/hash/")?.join(&t.as_ref().encode())?)?.json()?)
+        json(self.get_ok(&endpoint::block(t.as_ref()))?)
     }
 
     pub fn height<T: AsRef<Height>>(&self, t: T) -> Result<Block, Error> {
-        Ok(reqwest::get(self.url.join("block/height/")?.join(&t.as_ref().to_string())?)?.json()?)
+        json(self.get_ok(&endpoint::height(t.as_ref()))?)
+    }
+
+    pub fn hash_at_height(&self, h: Height) -> Result<BlockHash, Error> {
+        let s = self.get_raw(&endpoint::height_field(&h, "indep_hash"))?;
+        BlockHash::decode(s.trim().trim_matches('"'))
+    }
+
+    pub fn blocks_back(&self, from: BlockHash) -> impl Iterator<Item = Result<Block, Error>> + '_ {
+        BlocksBack { client: self, next: Some(from) }
     }
 
     pub fn current_block(&self) -> Result<Block, Error> {
-        Ok(reqwest::get(self.url.join("block/current")?)?.json()?)
+        json(self.get_ok(&endpoint::current_block())?)
+    }
+
+    pub fn weave_size(&self) -> Result<u64, Error> {
+        self.current_block()?.weave_size.ok_or_else(|| Error::value_not_present("weave_size", "current block"))
+    }
+
+    /// The index has an entry for every block, newest first, and is read fully into memory.
+    pub fn block_index(&self) -> Result<Vec<BlockIndexEntry>, Error> {
+        json(self.get_ok(&endpoint::block_index())?)
+    }
+
+    pub fn wait_for_new_block(&self, current: &BlockHash, poll_interval: Duration) -> Result<Block, Error> {
+        loop {
+            let b = self.current_block()?;
+            if &b.indep != current {
+                return Ok(b)
+            }
+            std::thread::sleep(poll_interval);
+        }
+    }
+
+    pub fn blocks_since(&self, from: Height) -> Vec<Result<Block, Error>> {
+        match self.info() {
+            Ok(i) => Height::range(from, i.height).map(|h| self.height(h)).collect(),
+            Err(e) => vec![Err(e)],
+        }
     }
 
     pub fn tx<T: AsRef<TxHash>>(&self, t: T) -> Result<Tx, Error> {
-        Ok(reqwest::get(self.url.join("tx/")?.join(&t.as_ref().encode())?)?.json()?)
+        json(self.get_ok(&endpoint::tx(t.as_ref()))?)
+    }
+
+    pub fn tx_field<T: AsRef<TxHash>>(&self, t: T, field: &str) -> Result<String, Error> {
+        self.get_ok(&endpoint::tx_field(t.as_ref(), field))?.text()
+    }
+
+    pub fn tx_quantity<T: AsRef<TxHash>>(&self, t: T) -> Result<Winstons, Error> {
+        Winstons::decode(self.tx_field(t, "quantity")?)
+    }
+
+    pub fn tx_reward<T: AsRef<TxHash>>(&self, t: T) -> Result<Winstons, Error> {
+        Winstons::decode(self.tx_field(t, "reward")?)
+    }
+
+    pub fn txs(&self, hashes: &[TxHash]) -> Vec<Result<Tx, Error>> {
+        let next = AtomicUsize::new(0);
+        let workers = MAX_CONCURRENT_REQUESTS.min(hashes.len());
+        let mut rs = std::thread::scope(|s| {
+            let hs = (0..workers).map(|_| s.spawn(|| {
+                let mut rs = Vec::new();
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    match hashes.get(i) {
+                        Some(txh) => rs.push((i, self.tx(txh))),
+                        None => return rs,
+                    }
+                }
+            })).collect::<Vec<_>>();
+            hs.into_iter().flat_map(|h| h.join().expect("worker panicked")).collect::<Vec<_>>()
+        });
+        rs.sort_by_key(|(i, _)| *i);
+        rs.into_iter().map(|(_, r)| r).collect()
+    }
+
+    pub fn data<T: AsRef<TxHash>>(&self, t: T) -> Result<Vec<u8>, Error> {
+        let rsp = self.get(&endpoint::data(t.as_ref()))?;
+        match rsp.status {
+            200 => rsp.bytes(),
+            202 | 404 => Err(Error::value_not_present("data", &format!("transaction {}", t.as_ref()))),
+            _ => rsp.fail(),
+        }
+    }
+
+    pub fn resolve(&self, ar_url: &str) -> Result<Vec<u8>, Error> {
+        self.data(endpoint::parse_ar_url(ar_url)?)
+    }
+
+    /// The returned reader yields the data as it arrives from the gateway.
+    pub fn data_stream<T: AsRef<TxHash>>(&self, t: T) -> Result<impl Read, Error> {
+        let rsp = self.get(&endpoint::data(t.as_ref()))?;
+        match rsp.status {
+            200 => Ok(match &self.progress {
+                Some(f) => Box::new(Progress { inner: rsp.body, done: 0, total: rsp.len.unwrap_or(0), f: f.clone() }),
+                None => rsp.body,
+            }),
+            202 | 404 => Err(Error::value_not_present("data", &format!("transaction {}", t.as_ref()))),
+            _ => rsp.fail(),
+        }
+    }
+
+    pub fn tx_offset<T: AsRef<TxHash>>(&self, t: T) -> Result<TxOffset, Error> {
+        let rsp = self.get(&endpoint::tx_field(t.as_ref(), "offset"))?;
+        match rsp.status {
+            200 => json(rsp),
+            404 => Err(Error::value_not_present("offset", &format!("transaction {}", t.as_ref()))),
+            _ => rsp.fail(),
+        }
     }
 
-    pub fn submit<T: AsRef<Tx>>(&self, t: T) -> Result<(), Error> {
-        let client = reqwest::Client::new();
-        client.post(self.url.join("tx")?).json(t.as_ref()).send()?;
+    pub fn tx_status<T: AsRef<TxHash>>(&self, t: T) -> Result<TxStatus, Error> {
+        let rsp = self.get(&endpoint::tx_status(t.as_ref()))?;
+        match rsp.status {
+            200 => Ok(TxStatus::from(json::<Confirmation>(rsp)?)),
+            202 => Ok(TxStatus::Pending),
+            404 => Ok(TxStatus::NotFound),
+            _ => rsp.fail(),
+        }
+    }
+
+    pub fn pending(&self) -> Result<Vec<TxHash>, Error> {
+        json(self.get_ok(&endpoint::pending())?)
+    }
+
+    pub fn tx_anchor(&self) -> Result<Anchor, Error> {
+        Anchor::decode(self.get_ok(&endpoint::tx_anchor())?.text()?)
+    }
+
+    pub fn submit<T: AsRef<Tx>>(&self, t: T) -> Result<TxHash, Error> {
+        self.check_network()?;
+        self.post(&endpoint::submit(), t.as_ref())?;
+        Ok(t.as_ref().id.clone())
+    }
+
+    pub fn post_chunk<T: AsRef<Chunk>>(&self, t: T) -> Result<(), Error> {
+        self.post(&endpoint::chunk(), t.as_ref())?;
+        Ok(())
+    }
+
+    pub fn upload_chunks<T: AsRef<Tx>>(&self, t: T) -> Result<(), Error> {
+        let tx = t.as_ref();
+        let data = tx.data.as_bytes();
+        let tree = DataTree::from_bytes(data);
+        if data.is_empty() || tx.data_root() != Some(tree.root()) {
+            return Err(Error::invalid_value("transaction", "data is missing or does not match the data root"))
+        }
+        for i in 0..tree.len() {
+            self.post_chunk(tree.chunk(data, i).expect("chunk in range"))?;
+            if let (Some(f), Some((_, end))) = (&self.progress, tree.range(i)) {
+                f(end as u64, data.len() as u64);
+            }
+        }
         Ok(())
     }
 
+    pub fn submit_and_wait<T: AsRef<Tx>>(&self, t: T, confirmations: u64,
+        poll_interval: Duration, timeout: Duration) -> Result<TxStatus, Error> {
+        let txh = self.submit(&t)?;
+        let started = Instant::now();
+        loop {
+            let status = self.tx_status(&txh)?;
+            match status {
+                TxStatus::Confirmed { number_of_confirmations, .. }
+                    if number_of_confirmations >= confirmations => return Ok(status),
+                TxStatus::Pending if confirmations == 0 => return Ok(status),
+                _ if started.elapsed() >= timeout => return Err(Error::Timeout(timeout)),
+                _ => std::thread::sleep(poll_interval),
+            }
+        }
+    }
+
     pub fn balance<T: AsRef<Address>>(&self, t: T) -> Result<Winstons, Error> {
-        let url = self.url.join(&format!("wallet/{}/balance", t.as_ref().encode()))?;
-        Ok(Winstons::decode(reqwest::get(url)?.text()?)?)
+        Winstons::decode(self.get_ok(&endpoint::balance(t.as_ref()))?.text()?)
+    }
+
+    pub fn balance_and_price(&self, addr: &Address, size: usize) -> Result<(Winstons, Winstons), Error> {
+        let (b, p) = std::thread::scope(|s| {
+            let b = s.spawn(|| self.balance(addr));
+            let p = self.price(None::<&Address>, size);
+            (b.join().expect("worker panicked"), p)
+        });
+        Ok((b?, p?))
+    }
+
+    pub fn last_tx<T: AsRef<Address>>(&self, t: T) -> Result<Option<TxHash>, Error> {
+        TxHash::decode_optional(self.get_ok(&endpoint::last_tx(t.as_ref()))?.text()?)
     }
 
     pub fn price<T: AsRef<Address>>(&self, t: Option<T>, size: usize) -> Result<Winstons, Error> {
-        let url = match t {
-            Some(target) => self.url.join(&format!("price/{}/{}", size, target.as_ref().encode()))?,
-            None => self.url.join(&format!("price/{}", size))?,
+        let t = t.as_ref().map(AsRef::as_ref);
+        let cache = match &self.price_cache {
+            Some(c) => c,
+            None => return Winstons::decode(self.get_ok(&endpoint::price(t, size))?.text()?),
         };
-        Ok(Winstons::decode(reqwest::get(url)?.text()?)?)
+
+        let chunks = size.div_ceil(MAX_CHUNK_SIZE);
+        let key = (chunks, t.map(Address::encode));
+        if let Some((at, p)) = cache.entries.lock().unwrap().get(&key) {
+            if at.elapsed() < cache.ttl {
+                return Ok(p.clone())
+            }
+        }
+
+        // quote the largest size in the bucket so a cached price covers every size it is reused for
+        let p = Winstons::decode(self.get_ok(&endpoint::price(t, chunks * MAX_CHUNK_SIZE))?.text()?)?;
+        let mut entries = cache.entries.lock().unwrap();
+        entries.retain(|_, (at, _)| at.elapsed() < cache.ttl);
+        entries.insert(key, (Instant::now(), p.clone()));
+        Ok(p)
+    }
+
+    /// An estimate only: the fee actually charged is whatever /price quotes when the transaction is built.
+    pub fn estimate_cost(&self, bytes: usize) -> Result<Winstons, Error> {
+        self.price(None::<&Address>, bytes)
+    }
+
+    pub fn graphql(&self, query: &str, variables: serde_json::Value) -> Result<serde_json::Value, Error> {
+        let body = serde_json::json!({ "query": query, "variables": variables });
+        graphql::data(json(self.post(&endpoint::graphql(), &body)?)?)
+    }
+
+    fn transactions(&self, filter: &Filter, after: Option<String>) -> Result<TxPage, Error> {
+        graphql::page(self.graphql(graphql::TRANSACTIONS, filter.variables(after))?)
+    }
+
+    pub fn wallet_txs(&self, address: &Address) -> impl Iterator<Item = Result<TxHash, Error>> + '_ {
+        WalletTxs {
+            client: self,
+            filters: vec![Filter::recipient(address), Filter::owner(address)],
+            next_page: None,
+            buffer: VecDeque::new(),
+            seen: HashSet::new(),
+        }
+    }
+
+    pub fn query_by_tags(&self, tags: &[(&str, &str)], after: Option<String>) -> Result<TxPage, Error> {
+        self.transactions(&Filter::tags(tags), after)
+    }
+
+    pub fn query_by_owner(&self, a: &Address, after: Option<String>) -> Result<TxPage, Error> {
+        self.transactions(&Filter::owner(a), after)
+    }
+
+    pub fn query_by_recipient(&self, a: &Address, after: Option<String>) -> Result<TxPage, Error> {
+        self.transactions(&Filter::recipient(a), after)
+    }
+
+    pub fn price_for_builder(&self, builder: &TxBuilder) -> Result<Winstons, Error> {
+        self.price(builder.target.as_ref(), builder.byte_size())
+    }
+}
+
+impl Gateway for Client {
+    fn price(&self, target: Option<&Address>, size: usize) -> Result<Winstons, Error> {
+        Client::price(self, target, size)
+    }
+
+    fn tx_anchor(&self) -> Result<Anchor, Error> {
+        Client::tx_anchor(self)
+    }
+
+    fn balance(&self, address: &Address) -> Result<Winstons, Error> {
+        Client::balance(self, address)
     }
 }
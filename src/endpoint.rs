@@ -0,0 +1,76 @@
+use reqwest::Url;
+
+use crate::types::*;
+use crate::error::Error;
+
+pub fn default_target() -> String {
+    std::env::var("ARWEAVE_TARGET").unwrap_or_else(|_| "https://arweave.net".to_string())
+}
+
+pub fn parse<U: AsRef<str>>(u: U) -> Result<Url, Error> {
+    Ok(base(Url::parse(u.as_ref())?))
+}
+
+// joining replaces the last path segment unless the path ends in a slash
+pub fn base(mut u: Url) -> Url {
+    if !u.path().ends_with('/') {
+        let p = format!("{}/", u.path());
+        u.set_path(&p);
+    }
+    u
+}
+
+pub fn parse_ar_url(u: &str) -> Result<TxHash, Error> {
+    let id = match u.find("://") {
+        Some(i) if u[..i].eq_ignore_ascii_case("ar") => &u[i + 3..],
+        _ => return Err(Error::invalid_value("ar:// url", "expected the ar:// scheme")),
+    };
+    let id = id.strip_suffix('/').unwrap_or(id);
+    if id.is_empty() {
+        return Err(Error::invalid_value("ar:// url", "missing transaction id"))
+    }
+    TxHash::decode(id)
+}
+
+pub fn info() -> String { "info".to_string() }
+
+pub fn peers() -> String { "peers".to_string() }
+
+pub fn block(bh: &BlockHash) -> String { format!("block/hash/{}", bh.encode()) }
+
+pub fn height(h: &Height) -> String { format!("block/height/{}", h) }
+
+pub fn height_field(h: &Height, field: &str) -> String { format!("block/height/{}/{}", h, field) }
+
+pub fn current_block() -> String { "block/current".to_string() }
+
+pub fn block_index() -> String { "block_index".to_string() }
+
+pub fn tx(txh: &TxHash) -> String { format!("tx/{}", txh.encode()) }
+
+pub fn tx_field(txh: &TxHash, field: &str) -> String { format!("tx/{}/{}", txh.encode(), field) }
+
+pub fn data(txh: &TxHash) -> String { txh.encode() }
+
+pub fn tx_status(txh: &TxHash) -> String { format!("tx/{}/status", txh.encode()) }
+
+pub fn pending() -> String { "tx/pending".to_string() }
+
+pub fn tx_anchor() -> String { "tx_anchor".to_string() }
+
+pub fn submit() -> String { "tx".to_string() }
+
+pub fn chunk() -> String { "chunk".to_string() }
+
+pub fn graphql() -> String { "graphql".to_string() }
+
+pub fn balance(a: &Address) -> String { format!("wallet/{}/balance", a.encode()) }
+
+pub fn last_tx(a: &Address) -> String { format!("wallet/{}/last_tx", a.encode()) }
+
+pub fn price(target: Option<&Address>, size: usize) -> String {
+    match target {
+        Some(a) => format!("price/{}/{}", size, a.encode()),
+        None => format!("price/{}", size),
+    }
+}
@@ -4,12 +4,16 @@ use std::convert::From;
 #[derive(Debug)]
 pub enum Error {
     IoError(std::io::Error),
-    UrlError(reqwest::UrlError),
+    UrlError(url::ParseError),
     ReqwestError(reqwest::Error),
+    JsonError(serde_json::Error),
     OpensslError(openssl::error::ErrorStack),
     VarError(std::env::VarError),
     InvalidValue { thing: String, msg: String },
     ValueNotPresent { value: String, thing: String },
+    HttpStatus { status: u16, body: String, url: String, headers: Box<reqwest::header::HeaderMap> },
+    Timeout(std::time::Duration),
+    NetworkMismatch { expected: String, actual: Option<String> },
 }
 
 impl Error {
@@ -27,11 +31,31 @@ impl fmt::Display for Error {
         match self {
             Error::IoError(e) => write!(f, "io: {}", e),
             Error::ReqwestError(e) => write!(f, "request: {}", e),
+            Error::JsonError(e) => write!(f, "json: {}", e),
             Error::UrlError(e) => write!(f, "url: {}", e),
             Error::OpensslError(e) => write!(f, "openssl: {}", e),
             Error::VarError(e) => write!(f, "envvar: {}", e),
             Error::InvalidValue { thing, msg } => write!(f, "parsing {}: {}", thing, msg),
             Error::ValueNotPresent { value, thing } => write!(f, "value {} not present in {}", value, thing),
+            Error::HttpStatus { status, body, url, .. } => write!(f, "http status {} from {}: {}", status, url, body),
+            Error::Timeout(d) => write!(f, "timed out after {:?}", d),
+            Error::NetworkMismatch { expected, actual: Some(a) } => write!(f, "expected network {}, gateway is on {}", expected, a),
+            Error::NetworkMismatch { expected, actual: None } => write!(f, "expected network {}, gateway does not report one", expected),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::IoError(e) => Some(e),
+            Error::ReqwestError(e) => Some(e),
+            Error::JsonError(e) => Some(e),
+            Error::UrlError(e) => Some(e),
+            Error::OpensslError(e) => Some(e),
+            Error::VarError(e) => Some(e),
+            Error::InvalidValue { .. } | Error::ValueNotPresent { .. } | Error::HttpStatus { .. }
+                | Error::Timeout(_) | Error::NetworkMismatch { .. } => None,
         }
     }
 }
@@ -44,8 +68,12 @@ impl From<reqwest::Error> for Error {
     fn from(e: reqwest::Error) -> Self { Error::ReqwestError(e) }
 }
 
-impl From<reqwest::UrlError> for Error {
-    fn from(e: reqwest::UrlError) -> Self { Error::UrlError(e) }
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self { Error::JsonError(e) }
+}
+
+impl From<url::ParseError> for Error {
+    fn from(e: url::ParseError) -> Self { Error::UrlError(e) }
 }
 
 impl From<openssl::error::ErrorStack> for Error {
@@ -0,0 +1,8 @@
+use crate::types::*;
+use crate::error::Error;
+
+pub trait Gateway {
+    fn price(&self, target: Option<&Address>, size: usize) -> Result<Winstons, Error>;
+    fn tx_anchor(&self) -> Result<Anchor, Error>;
+    fn balance(&self, address: &Address) -> Result<Winstons, Error>;
+}
@@ -0,0 +1,97 @@
+use serde::{Serialize, Deserialize};
+use serde_json::{json, Value};
+
+use crate::types::*;
+use crate::error::Error;
+
+pub const TRANSACTIONS: &str = "query($owners: [String!], $recipients: [String!], $tags: [TagFilter!], $after: String) {
+  transactions(owners: $owners, recipients: $recipients, tags: $tags, after: $after, first: 100) {
+    pageInfo { hasNextPage }
+    edges { cursor node { id } }
+  }
+}";
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TxPage {
+    pub txs: Vec<TxHash>,
+    pub cursor: Option<String>,
+}
+
+#[derive(Serialize)]
+struct TagFilter<'a> {
+    name: &'a str,
+    values: Vec<&'a str>,
+}
+
+#[derive(Serialize, Default)]
+pub(crate) struct Filter<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    owners: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    recipients: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tags: Option<Vec<TagFilter<'a>>>,
+}
+
+impl<'a> Filter<'a> {
+    pub fn tags(tags: &[(&'a str, &'a str)]) -> Self {
+        let tags = tags.iter().map(|(name, value)| TagFilter { name, values: vec![value] }).collect();
+        Filter { tags: Some(tags), ..Filter::default() }
+    }
+
+    pub fn owner(a: &Address) -> Self {
+        Filter { owners: Some(vec![a.encode()]), ..Filter::default() }
+    }
+
+    pub fn recipient(a: &Address) -> Self {
+        Filter { recipients: Some(vec![a.encode()]), ..Filter::default() }
+    }
+
+    pub fn variables(&self, after: Option<String>) -> Value {
+        let mut v = json!(self);
+        if let Some(after) = after {
+            v["after"] = json!(after);
+        }
+        v
+    }
+}
+
+#[derive(Deserialize)]
+struct Message { message: String }
+
+pub(crate) fn data(mut response: Value) -> Result<Value, Error> {
+    match serde_json::from_value::<Vec<Message>>(response["errors"].take()) {
+        Ok(es) if !es.is_empty() => {
+            let msg = es.into_iter().map(|e| e.message).collect::<Vec<_>>().join("; ");
+            Err(Error::invalid_value("graphql response", &msg))
+        },
+        _ => Ok(response["data"].take()),
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PageInfo { has_next_page: bool }
+
+#[derive(Deserialize)]
+struct Node { id: TxHash }
+
+#[derive(Deserialize)]
+struct Edge { cursor: String, node: Node }
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Transactions { page_info: PageInfo, edges: Vec<Edge> }
+
+#[derive(Deserialize)]
+struct Data { transactions: Transactions }
+
+pub(crate) fn page(data: Value) -> Result<TxPage, Error> {
+    let ts = serde_json::from_value::<Data>(data)
+        .map_err(|e| Error::invalid_value("graphql response", &e.to_string()))?.transactions;
+    let cursor = match ts.edges.last() {
+        Some(e) if ts.page_info.has_next_page => Some(e.cursor.to_owned()),
+        _ => None,
+    };
+    Ok(TxPage { txs: ts.edges.into_iter().map(|e| e.node.id).collect(), cursor })
+}
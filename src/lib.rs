@@ -4,6 +4,10 @@ extern crate reqwest;
 extern crate openssl;
 
 mod sponge;
+pub use crate::sponge::{deep_hash, DeepHashItem, Sponge, Absorbable, Collector};
+
+mod merkle;
+pub use crate::merkle::DataTree;
 
 mod types;
 pub use crate::types::*;
@@ -11,8 +15,27 @@ pub use crate::types::*;
 mod error;
 pub use crate::error::*;
 
+mod endpoint;
+
+pub use reqwest::Url;
+pub use reqwest::header::HeaderMap;
+
+mod graphql;
+pub use crate::graphql::TxPage;
+
+mod gateway;
+pub use crate::gateway::*;
+
+mod backend;
+pub use crate::backend::HttpBackend;
+
+#[cfg(feature = "blocking")]
 mod client;
+#[cfg(feature = "blocking")]
 pub use crate::client::*;
 
+mod async_client;
+pub use crate::async_client::*;
+
 mod tx_builder;
 pub use crate::tx_builder::*;
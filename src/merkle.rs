@@ -0,0 +1,140 @@
+use crate::types::{DataRoot, DataPath, Chunk, Data};
+
+use openssl::sha::sha256;
+
+// https://github.com/ArweaveTeam/arweave-js/blob/master/src/common/lib/merkle.ts
+pub const MAX_CHUNK_SIZE: usize = 256 * 1024;
+pub const MIN_CHUNK_SIZE: usize = 32 * 1024;
+
+enum Kind {
+    Leaf { data_hash: [u8; 32] },
+    Branch { left: Box<Node>, right: Box<Node> },
+}
+
+struct Node {
+    id: [u8; 32],
+    max_byte_range: usize,
+    kind: Kind,
+}
+
+fn note(n: usize) -> [u8; 32] {
+    let mut bs = [0u8; 32];
+    bs[24..].copy_from_slice(&(n as u64).to_be_bytes());
+    bs
+}
+
+fn chunk_ranges(size: usize) -> Vec<(usize, usize)> {
+    let mut ranges = vec![];
+    let mut cursor = 0;
+    while size - cursor >= MAX_CHUNK_SIZE {
+        let rest = size - cursor;
+        let mut chunk_size = MAX_CHUNK_SIZE;
+        let next = rest - MAX_CHUNK_SIZE;
+        if next > 0 && next < MIN_CHUNK_SIZE {
+            chunk_size = rest.div_ceil(2);
+        }
+        ranges.push((cursor, cursor + chunk_size));
+        cursor += chunk_size;
+    }
+    ranges.push((cursor, size));
+    ranges
+}
+
+fn leaf(data: &[u8], (min, max): (usize, usize)) -> Node {
+    let data_hash = sha256(&data[min..max]);
+    let id = sha256(&[sha256(&data_hash), sha256(&note(max))].concat());
+    Node { id, max_byte_range: max, kind: Kind::Leaf { data_hash } }
+}
+
+fn branch(left: Node, right: Node) -> Node {
+    let id = sha256(&[
+        sha256(&left.id),
+        sha256(&right.id),
+        sha256(&note(left.max_byte_range)),
+    ].concat());
+    let max_byte_range = right.max_byte_range;
+    Node { id, max_byte_range, kind: Kind::Branch { left: Box::new(left), right: Box::new(right) } }
+}
+
+fn build(data: &[u8], ranges: &[(usize, usize)]) -> Node {
+    let mut layer = ranges.iter().map(|r| leaf(data, *r)).collect::<Vec<_>>();
+    while layer.len() > 1 {
+        let mut next = vec![];
+        let mut nodes = layer.into_iter();
+        while let Some(left) = nodes.next() {
+            match nodes.next() {
+                Some(right) => next.push(branch(left, right)),
+                None => next.push(left),
+            }
+        }
+        layer = next;
+    }
+    layer.remove(0)
+}
+
+fn proofs(node: &Node, path: Vec<u8>, acc: &mut Vec<Vec<u8>>) {
+    match &node.kind {
+        Kind::Leaf { data_hash } => {
+            acc.push([path.as_slice(), data_hash, &note(node.max_byte_range)].concat())
+        },
+        Kind::Branch { left, right } => {
+            let path = [path.as_slice(), &left.id, &right.id, &note(left.max_byte_range)].concat();
+            proofs(left, path.clone(), acc);
+            proofs(right, path, acc);
+        },
+    }
+}
+
+pub fn data_root(data: &[u8]) -> Vec<u8> {
+    build(data, &chunk_ranges(data.len())).id.to_vec()
+}
+
+pub struct DataTree {
+    root: DataRoot,
+    size: usize,
+    chunks: Vec<((usize, usize), DataPath)>,
+}
+
+impl DataTree {
+    pub fn from_bytes(data: &[u8]) -> DataTree {
+        let ranges = chunk_ranges(data.len());
+        let root = build(data, &ranges);
+        let mut paths = vec![];
+        proofs(&root, vec![], &mut paths);
+
+        let mut chunks = ranges.into_iter().zip(paths.into_iter().map(DataPath::new)).collect::<Vec<_>>();
+        // the trailing empty chunk is part of the tree but never uploaded
+        if chunks.last().map(|((min, max), _)| min == max).unwrap_or(false) {
+            chunks.pop();
+        }
+
+        DataTree { root: DataRoot::new(root.id), size: data.len(), chunks }
+    }
+
+    pub fn root(&self) -> &DataRoot { &self.root }
+
+    pub fn size(&self) -> usize { self.size }
+
+    pub fn len(&self) -> usize { self.chunks.len() }
+
+    pub fn is_empty(&self) -> bool { self.chunks.is_empty() }
+
+    pub fn range(&self, chunk_index: usize) -> Option<(usize, usize)> {
+        self.chunks.get(chunk_index).map(|(r, _)| *r)
+    }
+
+    pub fn proof(&self, chunk_index: usize) -> Option<&DataPath> {
+        self.chunks.get(chunk_index).map(|(_, p)| p)
+    }
+
+    pub fn chunk(&self, data: &[u8], chunk_index: usize) -> Option<Chunk> {
+        let ((min, max), data_path) = self.chunks.get(chunk_index)?;
+        Some(Chunk {
+            data_root: self.root.clone(),
+            data_size: self.size as u64,
+            offset: max.saturating_sub(1) as u64,
+            data_path: data_path.clone(),
+            chunk: Data::from(data.get(*min..*max)?.to_vec()),
+        })
+    }
+}
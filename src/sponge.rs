@@ -1,6 +1,8 @@
 use crate::error::Error;
 
+use openssl::hash::MessageDigest;
 use openssl::rsa::{Padding};
+use openssl::sign::RsaPssSaltlen;
 use openssl::pkey::{PKeyRef, Public, Private};
 
 pub trait Sponge {
@@ -11,8 +13,12 @@ pub struct Verifier<'a> { v: openssl::sign::Verifier<'a> }
 
 impl<'a> Verifier<'a> {
     pub fn new(pk: &'a PKeyRef<Public>) -> Result<Verifier<'a>, Error> {
-        let mut v = openssl::sign::Verifier::new_without_digest(pk)?;
+        // RSA-PSS over SHA-256 with MGF1 SHA-256, accepting any salt length
+        // (arweave-js signs with 32 bytes in browsers and the maximum in node)
+        let mut v = openssl::sign::Verifier::new(MessageDigest::sha256(), pk)?;
         v.set_rsa_padding(Padding::PKCS1_PSS)?;
+        v.set_rsa_mgf1_md(MessageDigest::sha256())?;
+        v.set_rsa_pss_saltlen(RsaPssSaltlen::MAXIMUM_LENGTH)?;
         Ok(Verifier { v })
     }
 
@@ -31,8 +37,10 @@ pub struct Signer<'a> { s: openssl::sign::Signer<'a> }
 
 impl<'a> Signer<'a> {
     pub fn new(pk: &'a PKeyRef<Private>) -> Result<Self, Error> {
-        let mut s = openssl::sign::Signer::new_without_digest(pk)?;
+        let mut s = openssl::sign::Signer::new(MessageDigest::sha256(), pk)?;
         s.set_rsa_padding(Padding::PKCS1_PSS)?;
+        s.set_rsa_mgf1_md(MessageDigest::sha256())?;
+        s.set_rsa_pss_saltlen(RsaPssSaltlen::MAXIMUM_LENGTH)?;
         Ok(Signer { s })
     }
 
@@ -47,6 +55,83 @@ impl Sponge for Signer<'_> {
     }
 }
 
+pub struct Collector { bytes: Vec<u8> }
+
+impl Collector {
+    pub fn new() -> Self { Collector { bytes: vec![] } }
+
+    pub fn into_bytes(self) -> Vec<u8> { self.bytes }
+}
+
+impl Default for Collector {
+    fn default() -> Self { Collector::new() }
+}
+
+impl Sponge for Collector {
+    fn absorb<T: AsRef<[u8]>>(&mut self, t: T) -> Result<(), Error> {
+        self.bytes.extend_from_slice(t.as_ref());
+        Ok(())
+    }
+}
+
 pub trait Absorbable {
     fn squeeze<S: Sponge>(&self, s: &mut S) -> Result<(), Error>;
 }
+
+pub enum DeepHashItem {
+    Blob(Vec<u8>),
+    List(Vec<DeepHashItem>),
+}
+
+impl DeepHashItem {
+    pub fn blob<T: AsRef<[u8]>>(t: T) -> Self { DeepHashItem::Blob(t.as_ref().to_owned()) }
+
+    pub fn of<A: Absorbable>(a: &A) -> Result<Self, Error> {
+        let mut c = Collector::new();
+        a.squeeze(&mut c)?;
+        Ok(DeepHashItem::Blob(c.into_bytes()))
+    }
+}
+
+pub struct Sha384 { h: openssl::sha::Sha384 }
+
+impl Sha384 {
+    pub fn new() -> Self { Sha384 { h: openssl::sha::Sha384::new() } }
+
+    pub fn finish(self) -> Vec<u8> { self.h.finish().to_vec() }
+}
+
+impl Sponge for Sha384 {
+    fn absorb<T: AsRef<[u8]>>(&mut self, t: T) -> Result<(), Error> {
+        self.h.update(t.as_ref());
+        Ok(())
+    }
+}
+
+fn sha384<T: AsRef<[u8]>>(t: T) -> Result<Vec<u8>, Error> {
+    let mut h = Sha384::new();
+    h.absorb(t)?;
+    Ok(h.finish())
+}
+
+// https://github.com/ArweaveTeam/arweave/blob/master/apps/arweave/src/ar_deep_hash.erl
+pub fn deep_hash(item: &DeepHashItem) -> Result<Vec<u8>, Error> {
+    match item {
+        DeepHashItem::Blob(bs) => {
+            let mut h = Sha384::new();
+            h.absorb(sha384(format!("blob{}", bs.len()))?)?;
+            h.absorb(sha384(bs)?)?;
+            Ok(h.finish())
+        },
+        DeepHashItem::List(items) => {
+            let mut acc = sha384(format!("list{}", items.len()))?;
+            for i in items.iter() {
+                let mut h = Sha384::new();
+                h.absorb(acc)?;
+                h.absorb(deep_hash(i)?)?;
+                acc = h.finish();
+            }
+            Ok(acc)
+        },
+    }
+}
@@ -1,48 +1,47 @@
 use crate::types::*;
-use crate::sponge::{Sponge, Absorbable, Signer};
+use crate::sponge::{Sponge, Absorbable, Signer, Collector, DeepHashItem, deep_hash};
 use crate::error::Error;
-use crate::client::Client;
+use crate::gateway::Gateway;
 
+// https://github.com/ArweaveTeam/arweave/blob/master/apps/arweave/include/ar.hrl
+pub const MAX_INLINE_SIZE: usize = 10 * 1024 * 1024;
+
+#[derive(Clone)]
 pub struct TxBuilder {
+    format: u8,
     anchor: Anchor,
-    owner: Option<Owner>,
-    target: Option<Address>,
+    pub(crate) target: Option<Address>,
     data: Data,
     quantity: Winstons,
     reward: Option<Winstons>,
     tags: Tags,
-}
-
-impl Absorbable for TxBuilder {
-    fn squeeze<S: Sponge>(&self, s: &mut S) -> Result<(), Error> {
-        let owner = self.owner.as_ref().ok_or(Error::value_not_present("owner", "request builder"))?;
-        let reward = self.reward.as_ref().ok_or(Error::value_not_present("reward", "request builder"))?;
-
-        // https://github.com/ArweaveTeam/arweave/blob/d882d8a5880b765cd9a65928eaf7c04ea6aedfea/src/ar_tx.erl#L54
-        owner.squeeze(s)?;
-        if let Some(a) = self.target.as_ref() { a.squeeze(s)?; }
-        self.data.squeeze(s)?;
-        self.quantity.squeeze(s)?;
-        reward.squeeze(s)?;
-        self.anchor.squeeze(s)?;
-        self.tags.squeeze(s)?;
-        Ok(())
-    }
+    max_inline_size: usize,
+    allow_burn: bool,
 }
 
 impl TxBuilder {
     pub fn new(anchor: Anchor) -> Self {
         TxBuilder {
+            format: 1,
             anchor,
-            owner: None,
             target: None,
-            quantity: Winstons::from(0u32),
+            quantity: Winstons::zero(),
             reward: None,
             data: Data::from(vec![]),
             tags: Tags::new(),
+            max_inline_size: MAX_INLINE_SIZE,
+            allow_burn: false,
         }
     }
 
+    pub fn format(self, format: u8) -> Self {
+        TxBuilder { format, ..self }
+    }
+
+    pub fn anchor(self, anchor: Anchor) -> Self {
+        TxBuilder { anchor, ..self }
+    }
+
     pub fn target(self, target: Address) -> Self {
         TxBuilder { target: Some(target), ..self }
     }
@@ -51,35 +50,150 @@ impl TxBuilder {
         TxBuilder { data, ..self }
     }
 
+    pub fn data_str(mut self, s: &str) -> Self {
+        if !self.tags.contains("Content-Type") {
+            self.tags.push(Tag::from(("Content-Type", "text/plain")));
+        }
+        TxBuilder { data: Data::from(s), ..self }
+    }
+
     pub fn quantity(self, quantity: Winstons) -> Self {
         TxBuilder { quantity, ..self }
     }
 
-    pub fn reward(self, client: &Client) -> Result<Self, Error> {
-        let reward = Some(client.price(self.target.as_ref(), self.data.len())?);
+    pub fn tags(self, tags: Tags) -> Self {
+        TxBuilder { tags, ..self }
+    }
+
+    pub fn add_tag<T: Into<Tag>>(mut self, tag: T) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    pub fn content_type(mut self, mime: &str) -> Self {
+        self.tags.replace(Tag::from(("Content-Type", mime)));
+        self
+    }
+
+    pub fn max_inline_size(self, max_inline_size: usize) -> Self {
+        TxBuilder { max_inline_size, ..self }
+    }
+
+    pub fn allow_burn(self) -> Self {
+        TxBuilder { allow_burn: true, ..self }
+    }
+
+    pub fn reward_winstons(self, reward: Winstons) -> Self {
+        TxBuilder { reward: Some(reward), ..self }
+    }
+
+    pub fn reward(self, gateway: &impl Gateway) -> Result<Self, Error> {
+        let reward = Some(gateway.price(self.target.as_ref(), self.byte_size())?);
         Ok(TxBuilder { reward, ..self })
     }
 
-    pub fn sign<W: AsRef<Wallet>>(self, wallet: W) -> Result<Tx, Error> {
-        let txb = TxBuilder {
-            owner: Some(wallet.as_ref().owner().clone()?),
-            ..self
-        };
-        let mut s = Signer::new(wallet.as_ref().key())?;
-        txb.squeeze(&mut s)?;
-        let signature = Signature::new(s.sign()?)?;
+    pub fn byte_size(&self) -> usize {
+        self.data.len() + self.tags.iter()
+            .map(|t| t.name().as_bytes().len() + t.value().as_bytes().len()).sum::<usize>()
+    }
+
+    pub fn total_cost(&self) -> Result<Winstons, Error> {
+        let reward = self.reward.as_ref().ok_or(Error::value_not_present("reward", "request builder"))?;
+        Ok(&self.quantity + reward)
+    }
+
+    pub fn affordable_by(&self, gateway: &impl Gateway, wallet: &Wallet) -> Result<bool, Error> {
+        Ok(gateway.balance(wallet.address())? >= self.total_cost()?)
+    }
+
+    fn squeeze<S: Sponge>(&self, owner: &Owner, s: &mut S) -> Result<(), Error> {
+        let reward = self.reward.as_ref().ok_or(Error::value_not_present("reward", "request builder"))?;
+
+        // https://github.com/ArweaveTeam/arweave/blob/d882d8a5880b765cd9a65928eaf7c04ea6aedfea/src/ar_tx.erl#L54
+        owner.squeeze(s)?;
+        if let Some(a) = self.target.as_ref() { a.squeeze(s)?; }
+        self.data.squeeze(s)?;
+        self.quantity.squeeze(s)?;
+        reward.squeeze(s)?;
+        self.anchor.squeeze(s)?;
+        self.tags.squeeze(s)?;
+        Ok(())
+    }
+
+    fn deep_hash_item(&self, owner: &Owner, data_root: Option<&DataRoot>) -> Result<DeepHashItem, Error> {
+        let reward = self.reward.as_ref().ok_or(Error::value_not_present("reward", "request builder"))?;
+
+        // https://github.com/ArweaveTeam/arweave/blob/master/apps/arweave/src/ar_tx.erl
+        Ok(DeepHashItem::List(vec![
+            DeepHashItem::blob(self.format.to_string()),
+            DeepHashItem::of(owner)?,
+            self.target.as_ref().map(DeepHashItem::of).unwrap_or_else(|| Ok(DeepHashItem::blob([])))?,
+            DeepHashItem::of(&self.quantity)?,
+            DeepHashItem::of(reward)?,
+            DeepHashItem::of(&self.anchor)?,
+            self.tags.deep_hash_item(),
+            DeepHashItem::blob(self.data.len().to_string()),
+            data_root.map(DeepHashItem::of).unwrap_or_else(|| Ok(DeepHashItem::blob([])))?,
+        ]))
+    }
+
+    pub fn signing_data(&self, owner: &Owner) -> Result<Vec<u8>, Error> {
+        if self.target.is_none() && !self.quantity.is_zero() && !self.allow_burn {
+            return Err(Error::invalid_value("transaction quantity",
+                "a quantity without a target would be burned (use allow_burn to do so deliberately)"))
+        }
+
+        match self.format {
+            1 if self.data.len() > self.max_inline_size => Err(Error::invalid_value("transaction data", &format!(
+                "{} bytes exceeds the inline limit of {} bytes, use format 2 and upload the data in chunks",
+                self.data.len(), self.max_inline_size))),
+            1 => {
+                let mut c = Collector::new();
+                self.squeeze(owner, &mut c)?;
+                Ok(c.into_bytes())
+            },
+            2 => deep_hash(&self.deep_hash_item(owner, DataRoot::of(&self.data)?.as_ref())?),
+            f => Err(Error::invalid_value("transaction format", &format!("unsupported format {}", f))),
+        }
+    }
+
+    pub fn with_signature(self, owner: Owner, signature: Signature) -> Result<Tx, Error> {
         let id = signature.to_transaction_hash()?;
-        let reward = txb.reward.ok_or(Error::value_not_present("reward", "request builder"))?;
+        let reward = self.reward.ok_or(Error::value_not_present("reward", "request builder"))?;
+        let data_root = match self.format {
+            1 => None,
+            _ => DataRoot::of(&self.data)?,
+        };
         Ok(Tx {
-            anchor: txb.anchor,
-            data: txb.data,
+            format: self.format,
+            data_root: EmptyStringAsNone::from(data_root),
+            data_size: self.data.len() as u64,
+            anchor: self.anchor,
+            data: self.data,
             signature,
             id,
-            owner: wallet.as_ref().owner().clone()?,
-            quantity: txb.quantity,
-            reward: reward,
-            tags: txb.tags,
-            target: EmptyStringAsNone::from(txb.target),
+            owner,
+            quantity: self.quantity,
+            reward,
+            tags: self.tags,
+            target: EmptyStringAsNone::from(self.target),
         })
     }
+
+    fn signature(&self, wallet: &Wallet) -> Result<Signature, Error> {
+        let mut s = Signer::new(wallet.key())?;
+        s.absorb(self.signing_data(wallet.owner())?)?;
+        Signature::new(s.sign()?)
+    }
+
+    pub fn sign<W: AsRef<Wallet>>(self, wallet: W) -> Result<Tx, Error> {
+        let signature = self.signature(wallet.as_ref())?;
+        self.with_signature(wallet.as_ref().owner().clone(), signature)
+    }
+
+    /// Signing is salted, so only with_signature(owner, signature) yields a Tx with this id.
+    pub fn preview_id<W: AsRef<Wallet>>(&self, wallet: W) -> Result<(TxHash, Signature), Error> {
+        let signature = self.signature(wallet.as_ref())?;
+        Ok((signature.to_transaction_hash()?, signature))
+    }
 }
@@ -1,14 +1,17 @@
 use std::fmt;
 use std::convert::From;
 use std::marker::PhantomData;
+use std::path::Path;
+use std::str::FromStr;
 
 use crate::error::Error;
-use crate::sponge::{Sponge, Absorbable, Verifier};
+use crate::gateway::Gateway;
+use crate::sponge::{Sponge, Absorbable, Verifier, Signer, DeepHashItem, deep_hash};
 
 use chrono::{DateTime, Utc};
 use num_bigint::BigUint;
 use num_traits::cast::ToPrimitive;
-use openssl::bn::BigNum;
+use openssl::bn::{BigNum, BigNumRef};
 use openssl::hash::{MessageDigest, hash};
 use openssl::rsa::{Rsa};
 use openssl::pkey::{PKey, PKeyRef, Public, Private, HasPublic};
@@ -32,6 +35,10 @@ impl<T: Serialize> Serialize for EmptyStringAsNone<T> {
     }
 }
 
+impl<T> Default for EmptyStringAsNone<T> {
+    fn default() -> Self { Self(None) }
+}
+
 impl<T> From<Option<T>> for EmptyStringAsNone<T> {
     fn from(ot: Option<T>) -> Self { Self(ot) }
 }
@@ -51,7 +58,7 @@ where
     }
 
     fn visit_str<E: de::Error>(self, s: &str) -> Result<Self::Value, E> {
-        if s.len() == 0 {
+        if s.is_empty() {
             Ok(EmptyStringAsNone(None))
         } else {
             T::deserialize(s.into_deserializer()).map(Some).map(EmptyStringAsNone)
@@ -88,9 +95,10 @@ impl Bytes {
     }
 
     fn decode<T: AsRef<[u8]>>(thing: &'static str, t: T) -> Result<Bytes, Error> {
-        base64::decode_config(&t, base64::URL_SAFE_NO_PAD)
-            .map(|bytes| Bytes { thing, bytes }).map_err(|_| {
-                Error::invalid_value(thing, "invalid format (base64 URL-safe w/o padding)")
+        [base64::URL_SAFE_NO_PAD, base64::URL_SAFE, base64::STANDARD_NO_PAD, base64::STANDARD].iter()
+            .find_map(|c| base64::decode_config(&t, *c).ok())
+            .map(|bytes| Bytes { thing, bytes }).ok_or_else(|| {
+                Error::invalid_value(thing, "invalid format (base64)")
             })
     }
 
@@ -105,7 +113,7 @@ impl Bytes {
     }
 }
 
-fn is_human_readable(s: &String) -> bool {
+fn is_human_readable(s: &str) -> bool {
     s.chars().all(|c| {
         c.is_alphanumeric() || c.is_ascii_punctuation() || c.is_ascii_whitespace()
     })
@@ -114,7 +122,7 @@ fn is_human_readable(s: &String) -> bool {
 impl fmt::Debug for Bytes {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match String::from_utf8(self.bytes.to_owned()) {
-            Ok(ref s) if is_human_readable(&s) => write!(f, "{}", s),
+            Ok(ref s) if is_human_readable(s) => write!(f, "{}", s),
             _ => write!(f, "{}", self.encode()),
         }
     }
@@ -164,6 +172,59 @@ impl Serialize for Bytes {
 
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+struct Binary(Bytes);
+
+impl Absorbable for Binary {
+    fn squeeze<S: Sponge>(&self, s: &mut S) -> Result<(), Error> {
+        s.absorb(self.0.as_slice())
+    }
+}
+
+impl<'de> Deserialize<'de> for Binary {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        d.deserialize_str(BytesVisitor::new("binary")).map(Self)
+    }
+}
+
+mod number_or_string {
+    use super::*;
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+    where D: Deserializer<'de>, T: FromStr + From<u64>, T::Err: fmt::Display {
+        struct NumberOrStringVisitor<T> { marker: PhantomData<T> }
+        impl<'de, T> de::Visitor<'de> for NumberOrStringVisitor<T>
+        where T: FromStr + From<u64>, T::Err: fmt::Display {
+            type Value = Option<T>;
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a non-negative number or decimal string")
+            }
+
+            fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+                Ok(None)
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+                Ok(Some(T::from(v)))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                v.parse().map(Some).map_err(de::Error::custom)
+            }
+        }
+
+        deserializer.deserialize_any(NumberOrStringVisitor { marker: PhantomData })
+    }
+
+    pub fn serialize<S: Serializer, T: fmt::Display>(t: &Option<T>, s: S) -> Result<S::Ok, S::Error> {
+        match t {
+            Some(t) => s.serialize_str(&t.to_string()),
+            None => s.serialize_none(),
+        }
+    }
+}
+
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Serialize)]
 pub struct BlockHash(Bytes);
 
 impl BlockHash {
@@ -174,6 +235,14 @@ impl BlockHash {
     pub fn decode<T: AsRef<[u8]>>(t: T) -> Result<Self, Error> {
         Bytes::decode("block hash", t).and_then(|bs| bs.with_expected_length(48)).map(Self)
     }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+
+    pub fn from_bytes(bs: [u8; 48]) -> Self {
+        Self(Bytes::new("block hash", bs))
+    }
 }
 
 impl fmt::Display for BlockHash {
@@ -182,13 +251,18 @@ impl fmt::Display for BlockHash {
     }
 }
 
+impl FromStr for BlockHash {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Error> { Self::decode(s) }
+}
+
 impl AsRef<BlockHash> for BlockHash {
     #[inline] fn as_ref(&self) -> &Self { self }
 }
 
 impl Absorbable for BlockHash {
     fn squeeze<S: Sponge>(&self, s: &mut S) -> Result<(), Error> {
-        s.absorb(&self.0.as_slice())
+        s.absorb(self.0.as_slice())
     }
 }
 
@@ -199,7 +273,7 @@ impl<'de> Deserialize<'de> for BlockHash {
 }
 
 
-#[derive(Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 pub struct Height(u64);
 
 impl fmt::Display for Height {
@@ -208,13 +282,29 @@ impl fmt::Display for Height {
     }
 }
 
+impl Height {
+    pub fn next(&self) -> Self { Self(self.0.saturating_add(1)) }
+    pub fn prev(&self) -> Self { Self(self.0.saturating_sub(1)) }
+
+    pub fn checked_add(&self, other: Height) -> Option<Height> { self.0.checked_add(other.0).map(Self) }
+    pub fn saturating_add(&self, other: Height) -> Height { Self(self.0.saturating_add(other.0)) }
+
+    pub fn range(start: Height, end: Height) -> impl DoubleEndedIterator<Item = Height> {
+        (start.0..=end.0).map(Self)
+    }
+}
+
 impl From<u64> for Height {
     #[inline] fn from(n: u64) -> Self { Self(n) }
 }
 
+impl From<Height> for u64 {
+    #[inline] fn from(h: Height) -> Self { h.0 }
+}
+
 impl std::ops::Add for Height {
     type Output = Self;
-    fn add(self, other: Self) -> Self { Self(self.0 + other.0) }
+    fn add(self, other: Self) -> Self { self.saturating_add(other) }
 }
 
 impl std::ops::Sub for Height {
@@ -233,7 +323,122 @@ impl AsRef<Height> for Height {
 }
 
 
-#[derive(Deserialize, Debug)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub struct Nonce(Bytes);
+
+impl Nonce {
+    pub fn encode(&self) -> String {
+        self.0.encode()
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+impl fmt::Display for Nonce {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.encode())
+    }
+}
+
+impl Absorbable for Nonce {
+    fn squeeze<S: Sponge>(&self, s: &mut S) -> Result<(), Error> {
+        s.absorb(self.0.as_slice())
+    }
+}
+
+impl<'de> Deserialize<'de> for Nonce {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        d.deserialize_str(BytesVisitor::new("nonce")).map(Self)
+    }
+}
+
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub struct TxRoot(Bytes);
+
+impl TxRoot {
+    pub fn encode(&self) -> String {
+        self.0.encode()
+    }
+
+    pub fn decode<T: AsRef<[u8]>>(t: T) -> Result<Self, Error> {
+        Bytes::decode("tx root", t).and_then(|bs| bs.with_expected_length(32)).map(Self)
+    }
+}
+
+impl fmt::Display for TxRoot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.encode())
+    }
+}
+
+impl Absorbable for TxRoot {
+    fn squeeze<S: Sponge>(&self, s: &mut S) -> Result<(), Error> {
+        s.absorb(self.0.as_slice())
+    }
+}
+
+impl<'de> Deserialize<'de> for TxRoot {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        d.deserialize_str(BytesVisitor::new_with_expected_length("tx root", 32)).map(Self)
+    }
+}
+
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct Difficulty(BigUint);
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Error> {
+        BigUint::parse_bytes(s.as_bytes(), 10).map(Self).ok_or(
+            Error::invalid_value("difficulty", "invalid format"))
+    }
+}
+
+impl From<u64> for Difficulty {
+    #[inline] fn from(n: u64) -> Self { Self(BigUint::from(n)) }
+}
+
+
+mod unclaimed_as_none {
+    use super::*;
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Option<Address>, D::Error> {
+        let s = String::deserialize(d)?;
+        if s.is_empty() || s == "unclaimed" {
+            Ok(None)
+        } else {
+            Address::decode(s).map(Some).map_err(de::Error::custom)
+        }
+    }
+
+    pub fn serialize<S: Serializer>(a: &Option<Address>, s: S) -> Result<S::Ok, S::Error> {
+        match a {
+            Some(a) => a.serialize(s),
+            None => s.serialize_str("unclaimed"),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
+struct ProofOfAccess {
+    #[serde(default, with = "number_or_string")]
+    option: Option<u64>,
+    tx_path: Binary,
+    data_path: Binary,
+    chunk: Binary,
+}
+
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
 pub struct Block {
     #[serde(rename = "indep_hash")]
     pub indep: BlockHash,
@@ -242,23 +447,137 @@ pub struct Block {
     pub txs: Vec<TxHash>,
     #[serde(with = "chrono::serde::ts_seconds")]
     pub timestamp: DateTime<Utc>,
+    #[serde(default)]
+    pub nonce: Option<Nonce>,
+    #[serde(default)]
+    hash: Option<Binary>,
+    #[serde(default)]
+    last_retarget: Option<i64>,
+    #[serde(default, with = "number_or_string")]
+    pub diff: Option<Difficulty>,
+    #[serde(default, with = "number_or_string")]
+    pub cumulative_diff: Option<Difficulty>,
+    #[serde(default, with = "number_or_string")]
+    pub reward_pool: Option<Winstons>,
+    #[serde(default)]
+    wallet_list: Option<Binary>,
+    #[serde(default)]
+    hash_list_merkle: Option<Binary>,
+    #[serde(default)]
+    tx_root: EmptyStringAsNone<TxRoot>,
+    #[serde(default, with = "number_or_string")]
+    pub block_size: Option<u64>,
+    #[serde(default, with = "number_or_string")]
+    pub weave_size: Option<u64>,
+    #[serde(default, with = "unclaimed_as_none")]
+    pub reward_addr: Option<Address>,
+    #[serde(default)]
+    tags: Vec<Binary>,
+    #[serde(default)]
+    poa: Option<ProofOfAccess>,
+}
+
+// https://github.com/ArweaveTeam/arweave/blob/master/apps/arweave/src/ar_fork.erl
+const FORK_2_0: u64 = 422250;
+const FORK_2_4: u64 = 633720;
+
+fn required<'a, T>(t: &'a Option<T>, field: &str) -> Result<&'a T, Error> {
+    t.as_ref().ok_or_else(|| Error::value_not_present(field, "block"))
 }
 
 impl Block {
     pub fn previous_block(&self) -> Option<&BlockHash> {
         self.previous_block.as_option_ref()
     }
+
+    pub fn timestamp_secs(&self) -> i64 {
+        self.timestamp.timestamp()
+    }
+
+    pub fn tx_root(&self) -> Option<&TxRoot> {
+        self.tx_root.as_option_ref()
+    }
+
+    fn block_data_segment(&self) -> Result<Vec<u8>, Error> {
+        let poa = required(&self.poa, "poa")?;
+        let empty = || DeepHashItem::blob([]);
+
+        // https://github.com/ArweaveTeam/arweave/blob/master/apps/arweave/src/ar_block.erl
+        let base = deep_hash(&DeepHashItem::List(vec![
+            DeepHashItem::blob(self.height.to_string()),
+            self.previous_block().map(DeepHashItem::of).unwrap_or_else(|| Ok(empty()))?,
+            self.tx_root().map(DeepHashItem::of).unwrap_or_else(|| Ok(empty()))?,
+            DeepHashItem::List(self.txs.iter().map(DeepHashItem::of).collect::<Result<_, _>>()?),
+            DeepHashItem::blob(required(&self.block_size, "block_size")?.to_string()),
+            DeepHashItem::blob(required(&self.weave_size, "weave_size")?.to_string()),
+            self.reward_addr.as_ref().map(DeepHashItem::of)
+                .unwrap_or_else(|| Ok(DeepHashItem::blob("unclaimed")))?,
+            DeepHashItem::List(self.tags.iter().map(DeepHashItem::of).collect::<Result<_, _>>()?),
+            DeepHashItem::List(vec![
+                DeepHashItem::blob(required(&poa.option, "poa option")?.to_string()),
+                DeepHashItem::of(&poa.tx_path)?,
+                DeepHashItem::of(&poa.data_path)?,
+                DeepHashItem::of(&poa.chunk)?,
+            ]),
+        ]))?;
+
+        deep_hash(&DeepHashItem::List(vec![
+            DeepHashItem::Blob(base),
+            DeepHashItem::blob(self.timestamp.timestamp().to_string()),
+            DeepHashItem::blob(required(&self.last_retarget, "last_retarget")?.to_string()),
+            DeepHashItem::blob(required(&self.diff, "diff")?.to_string()),
+            DeepHashItem::blob(required(&self.cumulative_diff, "cumulative_diff")?.to_string()),
+            DeepHashItem::blob(required(&self.reward_pool, "reward_pool")?.to_string()),
+            DeepHashItem::of(required(&self.wallet_list, "wallet_list")?)?,
+            DeepHashItem::of(required(&self.hash_list_merkle, "hash_list_merkle")?)?,
+        ]))
+    }
+
+    pub fn verify_hash(&self) -> Result<bool, Error> {
+        if self.height.0 < FORK_2_0 || self.height.0 >= FORK_2_4 {
+            return Err(Error::invalid_value(
+                "block", "verifying the independent hash is supported for 2.0 to 2.3 blocks"))
+        }
+
+        let indep = deep_hash(&DeepHashItem::List(vec![
+            DeepHashItem::Blob(self.block_data_segment()?),
+            DeepHashItem::of(required(&self.hash, "hash")?)?,
+            DeepHashItem::of(required(&self.nonce, "nonce")?)?,
+        ]))?;
+        Ok(indep == self.indep.0.as_slice())
+    }
 }
 
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
+pub struct BlockIndexEntry {
+    pub hash: BlockHash,
+    #[serde(with = "u64_as_string")]
+    pub weave_size: u64,
+    #[serde(default)]
+    tx_root: EmptyStringAsNone<TxRoot>,
+}
 
-#[derive(Deserialize)]
+impl BlockIndexEntry {
+    pub fn tx_root(&self) -> Option<&TxRoot> {
+        self.tx_root.as_option_ref()
+    }
+}
+
+
+#[derive(Deserialize, Debug, Clone)]
 pub struct Info {
+    pub network: Option<String>,
+    pub version: Option<u64>,
+    pub release: Option<u64>,
     pub height: Height,
     pub current: BlockHash,
+    pub blocks: Option<u64>,
+    pub peers: Option<u64>,
+    pub queue_length: Option<u64>,
 }
 
 
-#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Serialize)]
 pub struct TxHash(Bytes);
 
 impl TxHash {
@@ -269,6 +588,18 @@ impl TxHash {
     pub fn decode<T: AsRef<[u8]>>(t: T) -> Result<Self, Error> {
         Bytes::decode("transaction hash", t).and_then(|bs| bs.with_expected_length(32)).map(Self)
     }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+
+    pub fn from_bytes(bs: [u8; 32]) -> Self {
+        Self(Bytes::new("transaction hash", bs))
+    }
+
+    pub(crate) fn decode_optional<T: AsRef<[u8]>>(t: T) -> Result<Option<Self>, Error> {
+        if t.as_ref().is_empty() { Ok(None) } else { Self::decode(t).map(Some) }
+    }
 }
 
 impl fmt::Display for TxHash {
@@ -277,13 +608,18 @@ impl fmt::Display for TxHash {
     }
 }
 
+impl FromStr for TxHash {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Error> { Self::decode(s) }
+}
+
 impl AsRef<TxHash> for TxHash {
     #[inline] fn as_ref(&self) -> &Self { self }
 }
 
 impl Absorbable for TxHash {
     fn squeeze<S: Sponge>(&self, s: &mut S) -> Result<(), Error> {
-        s.absorb(&self.0.as_slice())
+        s.absorb(self.0.as_slice())
     }
 }
 
@@ -294,15 +630,65 @@ impl<'de> Deserialize<'de> for TxHash {
 }
 
 
-#[derive(Debug, PartialEq, Eq, Serialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
 pub struct Data(Bytes);
 
 impl Data {
     pub fn len(&self) -> usize { self.0.len() }
 
+    pub fn is_empty(&self) -> bool { self.0.len() == 0 }
+
     pub fn decode<T: AsRef<[u8]>>(t: T) -> Result<Self, Error> {
         Bytes::decode("data", t).map(Self)
     }
+
+    pub fn as_bytes(&self) -> &[u8] { self.0.as_slice() }
+
+    pub fn as_str(&self) -> Result<&str, Error> {
+        std::str::from_utf8(self.as_bytes()).map_err(|_| Error::invalid_value("data", "not valid UTF-8"))
+    }
+
+    pub fn to_string_lossy(&self) -> std::borrow::Cow<'_, str> {
+        String::from_utf8_lossy(self.as_bytes())
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Ok(Data::from(std::fs::read(path)?))
+    }
+
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        Ok(std::fs::write(path, self.as_bytes())?)
+    }
+
+    pub fn guess_content_type<P: AsRef<Path>>(path: P) -> &'static str {
+        let ext = path.as_ref().extension().and_then(|e| e.to_str()).map(str::to_ascii_lowercase);
+        match ext.as_deref() {
+            Some("txt") => "text/plain",
+            Some("html") | Some("htm") => "text/html",
+            Some("css") => "text/css",
+            Some("csv") => "text/csv",
+            Some("md") => "text/markdown",
+            Some("js") | Some("mjs") => "application/javascript",
+            Some("json") => "application/json",
+            Some("xml") => "application/xml",
+            Some("pdf") => "application/pdf",
+            Some("zip") => "application/zip",
+            Some("gz") => "application/gzip",
+            Some("wasm") => "application/wasm",
+            Some("png") => "image/png",
+            Some("jpg") | Some("jpeg") => "image/jpeg",
+            Some("gif") => "image/gif",
+            Some("svg") => "image/svg+xml",
+            Some("webp") => "image/webp",
+            Some("ico") => "image/x-icon",
+            Some("mp3") => "audio/mpeg",
+            Some("ogg") => "audio/ogg",
+            Some("wav") => "audio/wav",
+            Some("mp4") => "video/mp4",
+            Some("webm") => "video/webm",
+            _ => "application/octet-stream",
+        }
+    }
 }
 
 impl AsRef<Data> for Data {
@@ -313,6 +699,14 @@ impl From<Vec<u8>> for Data {
     fn from(bytes: Vec<u8>) -> Data { Data(Bytes { thing: "data", bytes }) }
 }
 
+impl From<&str> for Data {
+    fn from(s: &str) -> Data { Data::from(Vec::from(s)) }
+}
+
+impl From<String> for Data {
+    fn from(s: String) -> Data { Data::from(s.into_bytes()) }
+}
+
 impl<'de> Deserialize<'de> for Data {
     fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
         d.deserialize_str(BytesVisitor::new("data")).map(Self)
@@ -321,19 +715,128 @@ impl<'de> Deserialize<'de> for Data {
 
 impl Absorbable for Data {
     fn squeeze<S: Sponge>(&self, s: &mut S) -> Result<(), Error> {
-        s.absorb(&self.0.as_slice())
+        s.absorb(self.0.as_slice())
     }
 }
 
 
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub struct DataRoot(Bytes);
+
+impl DataRoot {
+    pub fn encode(&self) -> String {
+        self.0.encode()
+    }
+
+    pub fn decode<T: AsRef<[u8]>>(t: T) -> Result<Self, Error> {
+        Bytes::decode("data root", t).and_then(|bs| bs.with_expected_length(32)).map(Self)
+    }
+
+    pub(crate) fn new<T: AsRef<[u8]>>(t: T) -> Self {
+        Self(Bytes::new("data root", t))
+    }
+
+    pub fn of(data: &Data) -> Result<Option<Self>, Error> {
+        if data.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(Self::new(crate::merkle::data_root(data.0.as_slice()))))
+        }
+    }
+}
+
+impl fmt::Display for DataRoot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.encode())
+    }
+}
+
+impl Absorbable for DataRoot {
+    fn squeeze<S: Sponge>(&self, s: &mut S) -> Result<(), Error> {
+        s.absorb(self.0.as_slice())
+    }
+}
+
+impl<'de> Deserialize<'de> for DataRoot {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        d.deserialize_str(BytesVisitor::new_with_expected_length("data root", 32)).map(Self)
+    }
+}
+
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub struct DataPath(Bytes);
+
+impl DataPath {
+    pub fn new<T: AsRef<[u8]>>(t: T) -> Self {
+        DataPath(Bytes::new("data path", t))
+    }
+
+    pub fn as_bytes(&self) -> &[u8] { self.0.as_slice() }
+
+    pub fn encode(&self) -> String {
+        self.0.encode()
+    }
+
+    pub fn decode<T: AsRef<[u8]>>(t: T) -> Result<Self, Error> {
+        Bytes::decode("data path", t).map(Self)
+    }
+}
+
+impl<'de> Deserialize<'de> for DataPath {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        d.deserialize_str(BytesVisitor::new("data path")).map(Self)
+    }
+}
+
+
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TxOffset {
+    #[serde(with = "u64_as_string")]
+    pub size: u64,
+    #[serde(with = "u64_as_string")]
+    pub offset: u64,
+}
+
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
+pub struct Chunk {
+    pub data_root: DataRoot,
+    #[serde(with = "u64_as_string")]
+    pub data_size: u64,
+    #[serde(with = "u64_as_string")]
+    pub offset: u64,
+    pub data_path: DataPath,
+    pub chunk: Data,
+}
+
+impl AsRef<Chunk> for Chunk {
+    #[inline] fn as_ref(&self) -> &Self { self }
+}
+
+
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub struct Winstons(BigUint);
 
 impl Winstons {
+    pub fn zero() -> Self { Self(BigUint::from(0u32)) }
+
+    pub fn is_zero(&self) -> bool { self.0 == BigUint::from(0u32) }
+
     pub fn decode<T: AsRef<[u8]>>(t: T) -> Result<Self, Error> {
+        if t.as_ref().is_empty() {
+            return Err(Error::invalid_value("a non-negative decimal number of Winstons", "empty value"))
+        }
         BigUint::parse_bytes(t.as_ref(), 10).map(Self).ok_or(
             Error::invalid_value("a non-negative decimal number of Winstons", "invalid format"))
     }
+
+    pub fn checked_sub(&self, other: &Winstons) -> Option<Winstons> {
+        if self.0 < other.0 {
+            None
+        } else {
+            Some(Self(&self.0 - &other.0))
+        }
+    }
 }
 
 impl fmt::Display for Winstons {
@@ -342,6 +845,11 @@ impl fmt::Display for Winstons {
     }
 }
 
+impl FromStr for Winstons {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Error> { Self::decode(s) }
+}
+
 impl std::ops::Add for Winstons {
     type Output = Winstons;
     fn add(self, other: Self) -> Self { Self(self.0 + other.0) }
@@ -352,6 +860,56 @@ impl std::ops::Add for &Winstons {
     fn add(self, other: Self) -> Winstons { Winstons(self.0.to_owned() + other.0.to_owned()) }
 }
 
+impl std::ops::Sub for Winstons {
+    type Output = Winstons;
+    fn sub(self, other: Self) -> Self { &self - &other }
+}
+
+impl std::ops::Sub for &Winstons {
+    type Output = Winstons;
+    fn sub(self, other: Self) -> Winstons {
+        self.checked_sub(other).unwrap_or_else(Winstons::zero)
+    }
+}
+
+impl PartialEq<u64> for Winstons {
+    fn eq(&self, other: &u64) -> bool { self.0 == BigUint::from(*other) }
+}
+
+impl PartialOrd<u64> for Winstons {
+    fn partial_cmp(&self, other: &u64) -> Option<std::cmp::Ordering> {
+        Some(self.0.cmp(&BigUint::from(*other)))
+    }
+}
+
+impl std::ops::AddAssign for Winstons {
+    fn add_assign(&mut self, other: Self) { self.0 += other.0 }
+}
+
+impl std::ops::AddAssign<&Winstons> for Winstons {
+    fn add_assign(&mut self, other: &Self) { self.0 += &other.0 }
+}
+
+impl std::ops::SubAssign for Winstons {
+    fn sub_assign(&mut self, other: Self) { *self -= &other }
+}
+
+impl std::ops::SubAssign<&Winstons> for Winstons {
+    fn sub_assign(&mut self, other: &Self) {
+        *self = self.checked_sub(other).unwrap_or_else(Winstons::zero)
+    }
+}
+
+impl std::ops::Mul<u64> for Winstons {
+    type Output = Winstons;
+    fn mul(self, n: u64) -> Self { Self(self.0 * n) }
+}
+
+impl std::ops::Mul<u64> for &Winstons {
+    type Output = Winstons;
+    fn mul(self, n: u64) -> Winstons { Winstons(&self.0 * n) }
+}
+
 impl<T> From<T> for Winstons where T: Into<BigUint> {
     #[inline] fn from(t: T) -> Self { Self(t.into()) }
 }
@@ -362,7 +920,7 @@ impl AsRef<Winstons> for Winstons {
 
 impl Absorbable for Winstons {
     fn squeeze<S: Sponge>(&self, s: &mut S) -> Result<(), Error> {
-        s.absorb(&self.0.to_str_radix(10).into_bytes())
+        s.absorb(self.0.to_str_radix(10).into_bytes())
     }
 }
 
@@ -389,25 +947,99 @@ pub mod winstons_as_strings {
     }
 }
 
+mod u64_as_string {
+    use super::*;
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        struct U64Visitor;
+        impl<'de> de::Visitor<'de> for U64Visitor {
+            type Value = u64;
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a non-negative decimal number")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                v.parse().map_err(de::Error::custom)
+            }
+        }
+
+        deserializer.deserialize_str(U64Visitor)
+    }
+
+    pub fn serialize<S: Serializer>(n: &u64, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&n.to_string())
+    }
+}
+
 pub mod winstons_as_numbers {
     use super::*;
 
-    pub fn deserialize<'de, D: Deserializer<'de>>(_deserializer: D) -> Result<Winstons, D::Error> {
-        unimplemented!()
+    // serialize falls back to a string beyond u64, so read those back as well
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Winstons, D::Error> {
+        winstons_flexible::deserialize(deserializer)
     }
 
     pub fn serialize<S: Serializer>(w: &Winstons, s: S) -> Result<S::Ok, S::Error> {
-        s.serialize_u64(w.0.to_u64().unwrap())
+        match w.0.to_u64() {
+            Some(n) => s.serialize_u64(n),
+            None => s.serialize_str(&w.0.to_string()),
+        }
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub mod winstons_flexible {
+    use super::*;
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Winstons, D::Error> {
+        struct WinstonsVisitor;
+        impl<'de> de::Visitor<'de> for WinstonsVisitor {
+            type Value = Winstons;
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a non-negative amount of Winstons as a number or decimal string")
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+                Ok(Winstons::from(v))
+            }
+
+            fn visit_u128<E: de::Error>(self, v: u128) -> Result<Self::Value, E> {
+                Ok(Winstons(BigUint::from(v)))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+                if v < 0 {
+                    Err(de::Error::invalid_value(de::Unexpected::Signed(v), &self))
+                } else {
+                    Ok(Winstons::from(v as u64))
+                }
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                Winstons::decode(v).map_err(de::Error::custom)
+            }
+        }
+
+        deserializer.deserialize_any(WinstonsVisitor)
+    }
+
+    pub fn serialize<S: Serializer>(w: &Winstons, s: S) -> Result<S::Ok, S::Error> {
+        winstons_as_strings::serialize(w, s)
+    }
+}
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Serialize)]
 pub struct Address(Bytes);
 
 impl Address {
     pub fn new<T: AsRef<[u8]>>(t: T) -> Result<Self, Error> {
-        let bs = Bytes::new("address", t).with_expected_length(32)?;
-        Ok(Address(bs))
+        let t = t.as_ref();
+        if t.len() != 32 {
+            let preview: String = t.iter().take(8).map(|b| format!("{:02x}", b)).collect();
+            return Err(Error::invalid_value("address", &format!(
+                "invalid length (is {}, should be 32): {}{}",
+                t.len(), preview, if t.len() > 8 { "..." } else { "" })))
+        }
+        Ok(Address(Bytes::new("address", t)))
     }
 
     pub fn encode(&self) -> String {
@@ -417,6 +1049,33 @@ impl Address {
     pub fn decode<T: AsRef<[u8]>>(t: T) -> Result<Self, Error> {
         Bytes::decode("address", t).and_then(|bs| bs.with_expected_length(32)).map(Self)
     }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+
+    pub fn from_bytes(bs: [u8; 32]) -> Self {
+        Self(Bytes::new("address", bs))
+    }
+
+    pub fn from_public_pem(pem: &str) -> Result<Self, Error> {
+        let pk = PKey::public_key_from_pem(pem.as_bytes())?;
+        Owner::from(&pk)?.address()
+    }
+
+    pub fn from_jwk_public(json: &str) -> Result<Self, Error> {
+        let jwk: JwkPublic = serde_json::from_str(json)
+            .map_err(|e| Error::invalid_value("JWK", &e.to_string()))?;
+        if jwk.kty != "RSA" {
+            return Err(Error::invalid_value("JWK", "unsupported key type"))
+        }
+        let rsa = Rsa::from_public_components(
+            decode_bignum("JWK modulus", &jwk.n)?,
+            decode_bignum("JWK public exponent", &jwk.e)?,
+        )?;
+        let pk = PKey::from_rsa(rsa)?;
+        Owner::from(&pk)?.address()
+    }
 }
 
 impl fmt::Display for Address {
@@ -425,13 +1084,18 @@ impl fmt::Display for Address {
     }
 }
 
+impl FromStr for Address {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Error> { Self::decode(s) }
+}
+
 impl AsRef<Address> for Address {
     #[inline] fn as_ref(&self) -> &Self { self }
 }
 
 impl Absorbable for Address {
     fn squeeze<S: Sponge>(&self, s: &mut S) -> Result<(), Error> {
-        s.absorb(&self.0.as_slice())
+        s.absorb(self.0.as_slice())
     }
 }
 
@@ -441,12 +1105,28 @@ impl<'de> Deserialize<'de> for Address {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub enum Anchor {
     Block(BlockHash),
     Transaction(Option<TxHash>),
 }
 
+impl Anchor {
+    pub fn decode<T: AsRef<str>>(t: T) -> Result<Self, Error> {
+        Anchor::deserialize(t.as_ref().into_deserializer())
+            .map_err(|e: de::value::Error| Error::invalid_value("anchor", &e.to_string()))
+    }
+
+    pub fn latest(gateway: &impl Gateway) -> Result<Self, Error> {
+        gateway.tx_anchor()
+    }
+}
+
+impl FromStr for Anchor {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Error> { Self::decode(s) }
+}
+
 impl Absorbable for Anchor {
     fn squeeze<S: Sponge>(&self, s: &mut S) -> Result<(), Error> {
         match &self {
@@ -467,7 +1147,7 @@ impl<'de> Deserialize<'de> for Anchor {
             }
 
             fn visit_str<E: de::Error>(self, s: &str) -> Result<Self::Value, E> {
-                if s.len() == 0 {
+                if s.is_empty() {
                     Ok(Anchor::Transaction(None))
                 } else {
                     BlockHash::deserialize(s.into_deserializer()).map(Anchor::Block)
@@ -494,18 +1174,18 @@ impl Serialize for Anchor {
 }
 
 
-#[derive(Debug, PartialEq)]
-pub struct Owner { n: BigNum }
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Owner { n: Bytes }
 
 impl Owner {
     pub fn address(&self) -> Result<Address, Error> {
-        hash(MessageDigest::sha256(), &self.n.to_vec()).map_err(Error::from)
+        hash(MessageDigest::sha256(), self.n.as_slice()).map_err(Error::from)
             .map(|bs| Address(Bytes { thing: "address", bytes: bs.to_vec() }))
     }
 
     pub fn pubkey(&self) -> Result<Rsa<Public>, Error> {
         // https://github.com/ArweaveTeam/arweave/blob/aef590a2e7fbc2703d47449c121058a77916ce16/src/ar_wallet.erl#L15
-        Ok(Rsa::from_public_components(self.n.to_owned()?, BigNum::from_u32(65537)?)?)
+        Ok(Rsa::from_public_components(BigNum::from_slice(self.n.as_slice())?, Self::exponent())?)
     }
 
     pub fn exponent() -> BigNum {
@@ -517,36 +1197,42 @@ impl Owner {
         if t.e().to_owned()? != Self::exponent() {
             Err(Error::invalid_value("RSA key", "incorrect public exponent"))
         } else {
-            Ok(Owner { n: t.n().to_owned()? })
+            let n = t.n().to_vec_padded(t.size() as i32)?;
+            Ok(Owner { n: Bytes { thing: "owner", bytes: n } })
         }
     }
 
-    pub fn clone(&self) -> Result<Self, Error> {
-        Ok(Owner { n: self.n.to_owned()? })
+    pub fn from_modulus<T: AsRef<[u8]>>(n: T) -> Result<Self, Error> {
+        if n.as_ref().is_empty() {
+            Err(Error::invalid_value("owner", "empty modulus"))
+        } else {
+            Ok(Owner { n: Bytes::new("owner", n) })
+        }
+    }
+
+    pub fn verify_message<T: AsRef<[u8]>>(&self, msg: T, sig: &Signature) -> Result<bool, Error> {
+        let pk = PKey::from_rsa(self.pubkey()?)?;
+        let mut v = Verifier::new(&pk)?;
+        v.absorb(msg)?;
+        v.verify(sig.0.as_slice())
     }
 }
 
 impl<'de> Deserialize<'de> for Owner {
     fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
-        d.deserialize_str(BytesVisitor::new("owner"))
-            .and_then(|bs| {
-                BigNum::from_slice(bs.as_slice()).map_err(Error::from).map_err(de::Error::custom)
-            })
-            .map(|n| Owner { n })
+        d.deserialize_str(BytesVisitor::new("owner")).map(|n| Owner { n })
     }
 }
 
 impl Absorbable for Owner {
     fn squeeze<S: Sponge>(&self, s: &mut S) -> Result<(), Error> {
-        s.absorb(&self.n.to_vec())
+        s.absorb(self.n.as_slice())
     }
 }
 
 impl Serialize for Owner {
     fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
-        let bs = self.n.to_vec();
-        let enc = base64::encode_config(&bs, base64::URL_SAFE_NO_PAD);
-        s.serialize_str(&enc)
+        self.n.serialize(s)
     }
 }
 
@@ -554,6 +1240,16 @@ impl Serialize for Owner {
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Serialize)]
 pub struct Name(Bytes);
 
+impl Name {
+    pub fn from_bytes(b: &[u8]) -> Name { Name(Bytes::new("tag name", b)) }
+
+    pub fn as_bytes(&self) -> &[u8] { self.0.as_slice() }
+
+    pub fn to_string_lossy(&self) -> std::borrow::Cow<'_, str> {
+        String::from_utf8_lossy(self.0.as_slice())
+    }
+}
+
 impl<'de> Deserialize<'de> for Name {
     fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
         d.deserialize_str(BytesVisitor::new("tag name")).map(Self)
@@ -564,10 +1260,24 @@ impl From<&str> for Name {
     fn from(s: &str) -> Name { Name(Bytes { thing: "tag name", bytes: Vec::from(s) }) }
 }
 
+impl From<String> for Name {
+    fn from(s: String) -> Name { Name(Bytes { thing: "tag name", bytes: s.into_bytes() }) }
+}
+
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize)]
 pub struct Value(Bytes);
 
+impl Value {
+    pub fn from_bytes(b: &[u8]) -> Value { Value(Bytes::new("tag value", b)) }
+
+    pub fn as_bytes(&self) -> &[u8] { self.0.as_slice() }
+
+    pub fn to_string_lossy(&self) -> std::borrow::Cow<'_, str> {
+        String::from_utf8_lossy(self.0.as_slice())
+    }
+}
+
 impl<'de> Deserialize<'de> for Value {
     fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
         d.deserialize_str(BytesVisitor::new("tag value")).map(Self)
@@ -578,10 +1288,19 @@ impl From<&str> for Value {
     fn from(s: &str) -> Value { Value(Bytes { thing: "tag value", bytes: Vec::from(s) }) }
 }
 
+impl From<String> for Value {
+    fn from(s: String) -> Value { Value(Bytes { thing: "tag value", bytes: s.into_bytes() }) }
+}
+
 
 #[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct Tag { name: Name, value: Value }
 
+impl Tag {
+    pub fn name(&self) -> &Name { &self.name }
+    pub fn value(&self) -> &Value { &self.value }
+}
+
 impl From<(Name, Value)> for Tag {
     fn from(kv: (Name, Value)) -> Tag { Tag { name: kv.0, value: kv.1 } }
 }
@@ -590,24 +1309,101 @@ impl From<(&str, &str)> for Tag {
     fn from(kv: (&str, &str)) -> Tag { Tag { name: Name::from(kv.0), value: Value::from(kv.1) } }
 }
 
+impl From<(String, String)> for Tag {
+    fn from(kv: (String, String)) -> Tag { Tag { name: Name::from(kv.0), value: Value::from(kv.1) } }
+}
+
 
 #[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone)]
 pub struct Tags(Vec<Tag>);
 
 impl Tags {
     pub fn new() -> Tags { Tags(vec![]) }
+
+    pub fn push(&mut self, tag: Tag) { self.0.push(tag) }
+
+    pub fn len(&self) -> usize { self.0.len() }
+
+    pub fn is_empty(&self) -> bool { self.0.is_empty() }
+
+    pub fn dedup_by_name(&mut self) {
+        let mut seen = std::collections::HashSet::new();
+        let mut ts = std::mem::take(&mut self.0);
+        ts.reverse();
+        ts.retain(|t| seen.insert(t.name.clone()));
+        ts.reverse();
+        self.0 = ts;
+    }
+
+    pub(crate) fn replace(&mut self, tag: Tag) {
+        self.0.retain(|t| t.name != tag.name);
+        self.0.push(tag)
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Tag> { self.0.iter() }
+
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.0.iter().find(|t| t.name.as_bytes() == name.as_bytes()).map(Tag::value)
+    }
+
+    pub fn contains(&self, name: &str) -> bool { self.get(name).is_some() }
+}
+
+impl Default for Tags {
+    fn default() -> Tags { Tags::new() }
+}
+
+impl IntoIterator for Tags {
+    type Item = Tag;
+    type IntoIter = std::vec::IntoIter<Tag>;
+    fn into_iter(self) -> std::vec::IntoIter<Tag> { self.0.into_iter() }
+}
+
+impl<'a> IntoIterator for &'a Tags {
+    type Item = &'a Tag;
+    type IntoIter = std::slice::Iter<'a, Tag>;
+    fn into_iter(self) -> std::slice::Iter<'a, Tag> { self.0.iter() }
+}
+
+impl<T: Into<Tag>> std::iter::FromIterator<T> for Tags {
+    fn from_iter<I: IntoIterator<Item = T>>(it: I) -> Tags {
+        Tags(it.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<Tag>> Extend<T> for Tags {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, it: I) {
+        self.0.extend(it.into_iter().map(Into::into))
+    }
 }
 
 impl From<Vec<Tag>> for Tags {
     fn from(ts: Vec<Tag>) -> Tags { Tags(ts) }
 }
 
+impl From<Vec<(Name, Value)>> for Tags {
+    fn from(ts: Vec<(Name, Value)>) -> Tags {
+        Tags(ts.into_iter().map(Tag::from).collect())
+    }
+}
+
 impl From<Vec<(&str, &str)>> for Tags {
     fn from(ts: Vec<(&str, &str)>) -> Tags {
         Tags(ts.iter().cloned().map(Tag::from).collect())
     }
 }
 
+impl Tags {
+    pub(crate) fn deep_hash_item(&self) -> DeepHashItem {
+        DeepHashItem::List(self.0.iter().map(|t| {
+            DeepHashItem::List(vec![
+                DeepHashItem::blob(t.name.0.as_slice()),
+                DeepHashItem::blob(t.value.0.as_slice()),
+            ])
+        }).collect())
+    }
+}
+
 impl Absorbable for Tags {
     fn squeeze<S: Sponge>(&self, s: &mut S) -> Result<(), Error> {
         for t in self.0.iter() {
@@ -619,7 +1415,7 @@ impl Absorbable for Tags {
 }
 
 
-#[derive(Debug, Serialize, PartialEq)]
+#[derive(Debug, Serialize, PartialEq, Clone)]
 pub struct Signature(Bytes);
 
 impl Signature {
@@ -627,8 +1423,16 @@ impl Signature {
         Ok(Signature(Bytes::new("signature", t)))
     }
 
+    pub fn encode(&self) -> String {
+        self.0.encode()
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+
     pub fn to_transaction_hash(&self) -> Result<TxHash, Error> {
-        hash(MessageDigest::sha256(), &self.0.as_slice()).map_err(Error::from)
+        hash(MessageDigest::sha256(), self.0.as_slice()).map_err(Error::from)
             .map(|bs| TxHash(Bytes { thing: "transaction hash", bytes: bs.to_vec() }))
     }
 }
@@ -637,19 +1441,29 @@ impl AsRef<Signature> for Signature {
     #[inline] fn as_ref(&self) -> &Self { self }
 }
 
+impl fmt::Display for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.encode())
+    }
+}
+
 impl<'de> Deserialize<'de> for Signature {
     fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
         d.deserialize_str(BytesVisitor::new("signature")).map(Self)
     }
 }
 
-#[derive(Deserialize, Serialize, Debug, PartialEq)]
+fn default_format() -> u8 { 1 }
+
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
 pub struct Tx {
+    #[serde(default = "default_format")]
+    pub format: u8,
     pub id: TxHash,
     pub data: Data,
-    #[serde(with = "winstons_as_strings")]
+    #[serde(with = "winstons_flexible")]
     pub quantity: Winstons,
-    #[serde(with = "winstons_as_strings")]
+    #[serde(with = "winstons_flexible")]
     pub reward: Winstons,
     pub target: EmptyStringAsNone<Address>,
     #[serde(rename = "last_tx")]
@@ -657,6 +1471,10 @@ pub struct Tx {
     pub owner: Owner,
     pub tags: Tags,
     pub signature: Signature,
+    #[serde(default)]
+    pub data_root: EmptyStringAsNone<DataRoot>,
+    #[serde(default, with = "u64_as_string")]
+    pub data_size: u64,
 }
 
 impl Absorbable for Tx {
@@ -682,25 +1500,239 @@ impl Tx {
         self.target.as_option_ref()
     }
 
+    pub fn data_root(&self) -> Option<&DataRoot> {
+        self.data_root.as_option_ref()
+    }
+
+    fn deep_hash_item(&self) -> Result<DeepHashItem, Error> {
+        // https://github.com/ArweaveTeam/arweave/blob/master/apps/arweave/src/ar_tx.erl
+        Ok(DeepHashItem::List(vec![
+            DeepHashItem::blob(self.format.to_string()),
+            DeepHashItem::of(&self.owner)?,
+            self.target().map(DeepHashItem::of).unwrap_or_else(|| Ok(DeepHashItem::blob([])))?,
+            DeepHashItem::of(&self.quantity)?,
+            DeepHashItem::of(&self.reward)?,
+            DeepHashItem::of(&self.anchor)?,
+            self.tags.deep_hash_item(),
+            DeepHashItem::blob(self.data_size.to_string()),
+            self.data_root().map(DeepHashItem::of).unwrap_or_else(|| Ok(DeepHashItem::blob([])))?,
+        ]))
+    }
+
+    pub fn verify_id(&self) -> Result<bool, Error> {
+        Ok(self.signature.to_transaction_hash()? == self.id)
+    }
+
     pub fn verify(&self) -> Result<bool, Error> {
+        if !self.verify_id()? {
+            return Ok(false)
+        }
+
         let pk = PKey::from_rsa(self.owner.pubkey()?)?;
         let mut v = Verifier::new(&pk)?;
-        self.squeeze(&mut v)?;
-        v.verify(&self.signature.0.as_slice())
+        match self.format {
+            1 => self.squeeze(&mut v)?,
+            2 => v.absorb(deep_hash(&self.deep_hash_item()?)?)?,
+            f => return Err(Error::invalid_value("transaction format", &format!("unsupported format {}", f))),
+        }
+        v.verify(self.signature.0.as_slice())
+    }
+
+    pub fn validate(&self) -> Result<(), Error> {
+        let invalid = |msg: &str| Err(Error::invalid_value("transaction", msg));
+
+        if self.format != 1 && self.format != 2 {
+            return invalid(&format!("unsupported format {}", self.format))
+        }
+        if !self.verify_id()? {
+            return invalid("id does not match the signature")
+        }
+
+        let pk = self.owner.pubkey()?;
+        if self.signature.as_bytes().len() != pk.size() as usize {
+            return invalid("signature length does not match the owner's key")
+        }
+        if self.target() == Some(&self.owner.address()?) {
+            return invalid("target is the owner's address")
+        }
+
+        // https://github.com/ArweaveTeam/arweave/blob/master/apps/arweave/src/ar_tx.erl
+        let tags_size: usize = self.tags.iter().map(|t| t.name().as_bytes().len() + t.value().as_bytes().len()).sum();
+        if tags_size > 2048 {
+            return invalid(&format!("tags are too large ({} bytes, at most 2048)", tags_size))
+        }
+
+        match self.format {
+            1 if self.data_root().is_some() || self.data_size != 0 => {
+                return invalid("format 1 transactions cannot have a data root or size")
+            },
+            2 if !self.data.is_empty() => {
+                if self.data_size != self.data.len() as u64 {
+                    return invalid("data size does not match the data")
+                }
+                if self.data_root().map(|r| r.0.as_slice()) != Some(crate::merkle::data_root(self.data.as_bytes()).as_slice()) {
+                    return invalid("data root does not match the data")
+                }
+            },
+            _ => (),
+        }
+
+        if !self.verify()? {
+            return invalid("signature does not verify")
+        }
+        Ok(())
+    }
+
+    pub fn verify_all(txs: &[Tx]) -> Vec<Result<bool, Error>> {
+        let workers = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+        let chunk = txs.len().div_ceil(workers).max(1);
+        std::thread::scope(|s| {
+            let hs = txs.chunks(chunk)
+                .map(|c| s.spawn(move || c.iter().map(Tx::verify).collect::<Vec<_>>()))
+                .collect::<Vec<_>>();
+            hs.into_iter().flat_map(|h| h.join().expect("worker panicked")).collect()
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum TxStatus {
+    Pending,
+    Confirmed {
+        block_height: Height,
+        block_indep_hash: BlockHash,
+        number_of_confirmations: u64,
+    },
+    NotFound,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct Confirmation {
+    block_height: Height,
+    block_indep_hash: BlockHash,
+    number_of_confirmations: u64,
+}
+
+impl From<Confirmation> for TxStatus {
+    fn from(c: Confirmation) -> TxStatus {
+        TxStatus::Confirmed {
+            block_height: c.block_height,
+            block_indep_hash: c.block_indep_hash,
+            number_of_confirmations: c.number_of_confirmations,
+        }
     }
 }
 
+#[derive(Deserialize, Serialize)]
+struct Jwk { kty: String, n: String, e: String, d: String, p: String, q: String, dp: String, dq: String, qi: String }
+
+#[derive(Deserialize)]
+struct JwkPublic { kty: String, n: String, e: String }
+
+fn decode_bignum(thing: &'static str, s: &str) -> Result<BigNum, Error> {
+    Bytes::decode(thing, s).and_then(|bs| BigNum::from_slice(bs.as_slice()).map_err(Error::from))
+}
+
+fn encode_bignum(thing: &str, n: Option<&BigNumRef>) -> Result<String, Error> {
+    n.map(|n| Bytes::new("JWK", n.to_vec()).encode())
+        .ok_or_else(|| Error::value_not_present(thing, "private key"))
+}
+
 pub struct Wallet { key: PKey<Private>, owner: Owner, address: Address  }
 
+impl fmt::Debug for Wallet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Wallet")
+            .field("address", &self.address.encode())
+            .field("key", &format_args!("<redacted private key>"))
+            .finish()
+    }
+}
+
 impl Wallet {
     pub fn address(&self) -> &Address { &self.address }
     pub fn new() -> Result<Self, Error> {
-        let key = PKey::from_rsa(Rsa::generate_with_e(4096, &Owner::exponent())?)?;
+        Self::with_key_size(4096)
+    }
+
+    pub fn with_key_size(bits: u32) -> Result<Self, Error> {
+        Self::from_key(PKey::from_rsa(Rsa::generate_with_e(bits, &Owner::exponent())?)?)
+    }
+
+    pub fn from_jwk(json: &str) -> Result<Self, Error> {
+        let jwk: Jwk = serde_json::from_str(json)
+            .map_err(|e| Error::invalid_value("JWK", &e.to_string()))?;
+        if jwk.kty != "RSA" {
+            return Err(Error::invalid_value("JWK", "unsupported key type"))
+        }
+        let rsa = Rsa::from_private_components(
+            decode_bignum("JWK modulus", &jwk.n)?,
+            decode_bignum("JWK public exponent", &jwk.e)?,
+            decode_bignum("JWK private exponent", &jwk.d)?,
+            decode_bignum("JWK first prime", &jwk.p)?,
+            decode_bignum("JWK second prime", &jwk.q)?,
+            decode_bignum("JWK first exponent", &jwk.dp)?,
+            decode_bignum("JWK second exponent", &jwk.dq)?,
+            decode_bignum("JWK coefficient", &jwk.qi)?,
+        )?;
+        if !rsa.check_key()? {
+            return Err(Error::invalid_value("JWK", "inconsistent RSA key"))
+        }
+        Self::from_key(PKey::from_rsa(rsa)?)
+    }
+
+    pub fn to_jwk(&self) -> Result<String, Error> {
+        let rsa = self.key.rsa()?;
+        let jwk = Jwk {
+            kty: "RSA".to_string(),
+            n: encode_bignum("modulus", Some(rsa.n()))?,
+            e: encode_bignum("public exponent", Some(rsa.e()))?,
+            d: encode_bignum("private exponent", Some(rsa.d()))?,
+            p: encode_bignum("first prime", rsa.p())?,
+            q: encode_bignum("second prime", rsa.q())?,
+            dp: encode_bignum("first exponent", rsa.dmp1())?,
+            dq: encode_bignum("second exponent", rsa.dmq1())?,
+            qi: encode_bignum("coefficient", rsa.iqmp())?,
+        };
+        serde_json::to_string(&jwk).map_err(|e| Error::invalid_value("JWK", &e.to_string()))
+    }
+
+    pub fn from_pem(pem: &[u8]) -> Result<Self, Error> {
+        let key = PKey::private_key_from_pem(pem)?;
+        if !key.rsa()?.check_key()? {
+            return Err(Error::invalid_value("PEM", "inconsistent RSA key"))
+        }
+        Self::from_key(key)
+    }
+
+    pub fn to_pem(&self) -> Result<Vec<u8>, Error> {
+        Ok(self.key.private_key_to_pem_pkcs8()?)
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let bs = std::fs::read(path)?;
+        let s = String::from_utf8_lossy(&bs);
+        if s.trim_start().starts_with("-----BEGIN") {
+            return Self::from_pem(&bs)
+        }
+        match serde_json::from_str::<serde_json::Value>(&s) {
+            Ok(v) if v["kty"] == "RSA" => Self::from_jwk(&s),
+            _ => Err(Error::invalid_value("wallet", "unrecognized format (tried JWK and PEM)")),
+        }
+    }
+
+    fn from_key(key: PKey<Private>) -> Result<Self, Error> {
         let owner = Owner::from(&key)?;
         let address = owner.address()?;
         Ok(Wallet { key, owner, address })
     }
 
+    pub fn sign_message<T: AsRef<[u8]>>(&self, msg: T) -> Result<Signature, Error> {
+        let mut s = Signer::new(self.key())?;
+        s.absorb(msg)?;
+        Signature::new(s.sign()?)
+    }
+
     pub fn owner(&self) -> &Owner { &self.owner }
     pub fn key(&self) -> &PKeyRef<Private> { self.key.as_ref() }
 }
@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 extern crate rand;
 
 use arweaver::{Address, Winstons};
@@ -7,6 +9,5 @@ pub fn address() -> Address {
 }
 
 pub fn quantity() -> Winstons {
-    let i = Winstons::from(rand::random::<u64>() % 100000000000);
-    Winstons::from(i)
+    Winstons::from(rand::random::<u64>() % 100000000000)
 }
@@ -1,12 +1,24 @@
+#![cfg(feature = "blocking")]
+
 use arweaver::*;
 mod settings;
 mod fresh;
+mod v1;
 
 #[test]
 fn info() {
     let c = Client::new().unwrap();
     let i = c.info().unwrap();
     assert!(i.height >= settings::recent_block_height());
+    assert_eq!(i.network.as_deref(), Some("arweave.N.1"));
+    assert!(i.release.unwrap() > 0);
+}
+
+#[test]
+fn peers() {
+    let c = Client::new().unwrap();
+    let ps = c.peers().unwrap();
+    assert!(ps.iter().all(|p| p.contains(':')));
 }
 
 #[test]
@@ -49,6 +61,14 @@ fn height() {
     assert!(b1.timestamp < b0.timestamp);
 }
 
+#[test]
+fn verify_block_hash() {
+    let c = Client::new().unwrap();
+    let b = c.height(settings::format_2_0_block_height()).unwrap();
+    assert!(b.verify_hash().unwrap());
+    assert!(c.current_block().unwrap().verify_hash().is_err());
+}
+
 #[test]
 fn genesis_block() {
     let c = Client::new().unwrap();
@@ -56,6 +76,29 @@ fn genesis_block() {
     assert!(b.previous_block().is_none());
 }
 
+#[test]
+fn blocks_back() {
+    let c = Client::new().unwrap();
+    let b = c.height(Height::from(3)).unwrap();
+    let bs = c.blocks_back(b.indep).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(bs.iter().map(|b| b.height).collect::<Vec<_>>(),
+        (0..4).rev().map(Height::from).collect::<Vec<_>>());
+}
+
+#[test]
+fn block_header() {
+    let c = Client::new().unwrap();
+    let b = c.current_block().unwrap();
+    assert!(b.nonce.is_some());
+    assert!(b.diff.is_some());
+    assert!(b.cumulative_diff >= b.diff);
+    assert!(b.reward_pool.is_some());
+    assert!(b.weave_size >= b.block_size);
+
+    let b1: Block = serde_json::from_str(&serde_json::to_string(&b).unwrap()).unwrap();
+    assert_eq!(b, b1);
+}
+
 #[test]
 fn txs() {
     let c = Client::new().unwrap();
@@ -72,6 +115,22 @@ fn txs() {
     }
 }
 
+#[test]
+fn txs_concurrently() {
+    let c = Client::new().unwrap();
+    let (bh, _) = settings::block_with_transactions();
+    let b = c.block(bh).unwrap();
+
+    let mut hs = b.txs.clone();
+    hs.push(TxHash::decode("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA").unwrap());
+    let rs = c.txs(&hs);
+    assert_eq!(rs.len(), hs.len());
+    for (txh, r) in b.txs.iter().zip(rs.iter()) {
+        assert_eq!(txh, &r.as_ref().unwrap().id);
+    }
+    assert!(rs.last().unwrap().is_err());
+}
+
 #[test]
 fn tx_data_style() {
     let c = Client::new().unwrap();
@@ -79,7 +138,7 @@ fn tx_data_style() {
     let t = c.tx(&txh).unwrap();
     assert_eq!(t.id, txh);
     assert_ne!(t.data.len(), 0);
-    assert_eq!(t.quantity, Winstons::from(0u32));
+    assert!(t.quantity.is_zero());
     assert_eq!(t.reward, r);
     assert_eq!(t.anchor, a);
     assert!(t.target().is_none());
@@ -87,6 +146,38 @@ fn tx_data_style() {
     assert!(t.verify().unwrap());
 }
 
+#[test]
+fn v1_preimage() {
+    let c = Client::new().unwrap();
+    let (txh, _, _, ts) = settings::data_transaction();
+    let json: serde_json::Value = serde_json::from_str(&c.get_raw(&format!("tx/{}", txh.encode())).unwrap()).unwrap();
+    let tx: Tx = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!((tx.format, &tx.tags), (1, &ts));
+
+    let mut s = Collector::new();
+    tx.squeeze(&mut s).unwrap();
+    let preimage = s.into_bytes();
+    assert_eq!(preimage, v1::preimage(&json));
+    assert!(v1::verify(&json, &preimage));
+}
+
+#[test]
+fn data() {
+    let c = Client::new().unwrap();
+    let (txh, _, _, _) = settings::data_transaction();
+    let t = c.tx(&txh).unwrap();
+    assert_eq!(c.data(&txh).unwrap().len(), t.data.len());
+}
+
+#[test]
+fn data_stream() {
+    let c = Client::new().unwrap();
+    let (txh, _, _, _) = settings::data_transaction();
+    let mut bs = Vec::new();
+    std::io::copy(&mut c.data_stream(&txh).unwrap(), &mut bs).unwrap();
+    assert_eq!(bs, c.data(&txh).unwrap());
+}
+
 #[test]
 fn tx_transfer_style() {
     let c = Client::new().unwrap();
@@ -103,9 +194,71 @@ fn tx_transfer_style() {
     assert!(t.verify().unwrap());
 }
 
+#[test]
+fn tx_field() {
+    let c = Client::new().unwrap();
+    let (txh, r, q, _, _, to) = settings::transfer_transaction();
+    assert_eq!(c.tx_quantity(&txh).unwrap(), q);
+    assert_eq!(c.tx_reward(&txh).unwrap(), r);
+    assert_eq!(c.tx_field(&txh, "target").unwrap(), to.encode());
+}
+
 #[test]
 fn balance() {
     let c = Client::new().unwrap();
-    assert_eq!(c.balance(fresh::address()).unwrap(), Winstons::from(0u32));
-    assert_ne!(c.balance(settings::account_with_nonzero_balance()).unwrap(), Winstons::from(0u32));
+    assert!(c.balance(fresh::address()).unwrap().is_zero());
+    assert!(!c.balance(settings::account_with_nonzero_balance()).unwrap().is_zero());
+}
+
+#[tokio::test]
+async fn async_info() {
+    let c = AsyncClient::new().unwrap();
+    let i = c.info().await.unwrap();
+    assert!(i.height >= settings::recent_block_height());
+}
+
+#[test]
+fn tx_status() {
+    let c = Client::new().unwrap();
+    let (txh, _, _, _) = settings::data_transaction();
+    match c.tx_status(&txh).unwrap() {
+        TxStatus::Confirmed { number_of_confirmations, .. } => assert!(number_of_confirmations > 0),
+        s => panic!("unexpected status: {:?}", s),
+    }
+}
+
+#[test]
+fn tx_anchor() {
+    let c = Client::new().unwrap();
+    assert_ne!(c.tx_anchor().unwrap(), Anchor::Transaction(None));
+}
+
+#[test]
+fn last_tx() {
+    let c = Client::new().unwrap();
+    assert_eq!(c.last_tx(fresh::address()).unwrap(), None);
+}
+
+#[test]
+fn price_for_builder() {
+    let c = Client::new().unwrap();
+    let txb = TxBuilder::new(Anchor::Transaction(None))
+        .data(Data::from(vec![0; 1000]))
+        .add_tag(("Content-Type", "application/octet-stream"));
+    assert_eq!(c.price_for_builder(&txb).unwrap(), c.price(None::<&Address>, txb.byte_size()).unwrap());
+}
+
+#[test]
+fn pending() {
+    let c = Client::new().unwrap();
+    assert!(c.pending().is_ok());
+}
+
+#[test]
+fn query_by_tags() {
+    let c = Client::new().unwrap();
+    let p = c.query_by_tags(&[("App-Name", "ArDrive-App")], None).unwrap();
+    assert!(!p.txs.is_empty());
+    let q = c.query_by_tags(&[("App-Name", "ArDrive-App")], p.cursor).unwrap();
+    assert!(!q.txs.contains(&p.txs[0]));
 }
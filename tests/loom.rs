@@ -1,3 +1,5 @@
+#![cfg(feature = "blocking")]
+
 use reqwest::Url;
 use arweaver::{Error, Address, Winstons, Tx, TxHash, winstons_as_numbers};
 use serde::{Serialize, Deserialize};
@@ -26,13 +28,13 @@ impl Client {
 
     pub fn faucet<A, Q>(&self, a: A, q: Q) -> Result<TxHash, Error>
     where A: AsRef<Address>, Q: AsRef<Winstons> {
-        let client = reqwest::Client::new();
+        let client = reqwest::blocking::Client::new();
         let req = FaucetReq { beneficiary: a.as_ref(), quantity: q.as_ref() };
-        let mut rsp = client.post(self.url.join("faucet")?).json(&req).send()?;
+        let rsp = client.post(self.url.join("faucet")?).json(&req).send()?;
         Ok(rsp.json::<FaucetRsp>()?.tx_id)
     }
 
     pub fn wait<T: AsRef<TxHash>>(&self, t: T) -> Result<Tx, Error> {
-        Ok(reqwest::get(self.url.join("wait/")?.join(&t.as_ref().encode())?)?.json()?)
+        Ok(reqwest::blocking::get(self.url.join("wait/")?.join(&t.as_ref().encode())?)?.json()?)
     }
 }
@@ -1,6 +1,9 @@
+#![cfg(feature = "blocking")]
+
 mod loom;
 mod fresh;
-use arweaver::{Winstons, Wallet, Anchor, TxBuilder};
+use std::time::Duration;
+use arweaver::{Wallet, Anchor, TxBuilder, Data, Address, TxStatus};
 
 #[test]
 fn faucet() {
@@ -9,7 +12,7 @@ fn faucet() {
 
     let a = fresh::address();
     let q = fresh::quantity();
-    assert_eq!(c.balance(&a).unwrap(), Winstons::from(0u32));
+    assert!(c.balance(&a).unwrap().is_zero());
     let _txh = l.faucet(&a, &q).unwrap();
     assert_eq!(c.balance(a).unwrap(), q);
 }
@@ -27,16 +30,57 @@ fn tx() {
     let _txh = l.faucet(w.address(), &q + &r).unwrap();
 
     assert_eq!(c.balance(w.address()).unwrap(), &q + &r);
-    assert_eq!(c.balance(&a).unwrap(), Winstons::from(0u32));
+    assert!(c.balance(&a).unwrap().is_zero());
 
     let tx = TxBuilder::new(Anchor::Transaction(None))
         .quantity(q.to_owned()).target(a.to_owned())
         .reward(&c).unwrap().sign(&w).unwrap();
 
-    c.submit(&tx).unwrap();
-    let tx0 = l.wait(&tx.id).unwrap();
+    let txh = c.submit(&tx).unwrap();
+    let tx0 = l.wait(&txh).unwrap();
     assert_eq!(tx, tx0);
 
-    assert_eq!(c.balance(w.address()).unwrap(), Winstons::from(0u32));
+    assert!(c.balance(w.address()).unwrap().is_zero());
     assert_eq!(c.balance(&a).unwrap(), q);
 }
+
+#[test]
+fn tagged_data_tx() {
+    let c = arweaver::Client::new().unwrap();
+    let l = loom::Client::new().unwrap();
+
+    let w = Wallet::new().unwrap();
+
+    let d = Data::from(b"hello".to_vec());
+    let r = c.price(None::<&Address>, d.len()).unwrap();
+    let _txh = l.faucet(w.address(), &r).unwrap();
+
+    let tx = TxBuilder::new(Anchor::Transaction(None))
+        .data(d).add_tag(("Content-Type", "text/plain"))
+        .reward(&c).unwrap().sign(&w).unwrap();
+    assert!(tx.verify().unwrap());
+
+    let txh = c.submit(&tx).unwrap();
+    let tx0 = l.wait(&txh).unwrap();
+    assert_eq!(tx.id, tx0.id);
+    assert_eq!(tx.tags, tx0.tags);
+    assert!(tx0.verify().unwrap());
+}
+
+#[test]
+fn submit_and_wait() {
+    let c = arweaver::Client::new().unwrap();
+    let l = loom::Client::new().unwrap();
+
+    let w = Wallet::new().unwrap();
+
+    let d = Data::from(b"hello".to_vec());
+    let r = c.price(None::<&Address>, d.len()).unwrap();
+    let _txh = l.faucet(w.address(), &r).unwrap();
+
+    let tx = TxBuilder::new(Anchor::Transaction(None))
+        .data(d).reward(&c).unwrap().sign(&w).unwrap();
+
+    let s = c.submit_and_wait(&tx, 1, Duration::from_secs(1), Duration::from_secs(120)).unwrap();
+    assert!(matches!(s, TxStatus::Confirmed { number_of_confirmations, .. } if number_of_confirmations >= 1));
+}
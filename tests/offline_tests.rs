@@ -0,0 +1,1413 @@
+#![cfg(feature = "blocking")]
+
+use arweaver::*;
+use serde::Deserialize;
+mod fresh;
+mod v1;
+
+#[test]
+fn client_with_url() {
+    assert!(Client::with_url("http://localhost:1984").is_ok());
+    assert!(matches!(Client::with_url("not a url"), Err(Error::UrlError(_))));
+}
+
+#[test]
+fn client_with_gateways() {
+    assert!(Client::with_gateways(vec![]).is_err());
+    let gs = vec![Url::parse("http://localhost:1984").unwrap(), Url::parse("https://arweave.net").unwrap()];
+    assert!(Client::with_gateways(gs).is_ok());
+}
+
+#[test]
+fn txs_unreachable() {
+    let c = Client::with_url("http://127.0.0.1:1").unwrap();
+    assert!(c.txs(&[]).is_empty());
+    let hs = vec![TxHash::decode("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA").unwrap(); 20];
+    let rs = c.txs(&hs);
+    assert_eq!(rs.len(), 20);
+    assert!(rs.iter().all(|r| matches!(r, Err(Error::ReqwestError(_)))));
+}
+
+#[test]
+fn anchor_decode() {
+    assert_eq!(Anchor::decode("").unwrap(), Anchor::Transaction(None));
+    let txh = "CCH2h2MzMP7WMh0Xf3GYL7zZDbU7E4CZPJWngp1qmDc";
+    assert_eq!(Anchor::decode(txh).unwrap(), Anchor::Transaction(Some(TxHash::decode(txh).unwrap())));
+    let bh = "2serU-303rThvozelaLz67ftihCw7cJPAEF40SkPkfyvz92Z5gCKVfhadoVU3ZRg";
+    assert_eq!(Anchor::decode(bh).unwrap(), Anchor::Block(BlockHash::decode(bh).unwrap()));
+    assert!(Anchor::decode("not an anchor!").is_err());
+    assert_eq!(txh.parse::<Anchor>().unwrap(), Anchor::decode(txh).unwrap());
+    assert!("not an anchor!".parse::<Anchor>().is_err());
+}
+
+#[test]
+fn winstons_as_numbers() {
+    #[derive(Deserialize)]
+    struct Q {
+        #[serde(with = "winstons_as_numbers")]
+        q: Winstons,
+    }
+
+    let q: Q = serde_json::from_str(r#"{"q":1234567}"#).unwrap();
+    assert_eq!(q.q, Winstons::from(1234567u64));
+    assert!(serde_json::from_str::<Q>(r#"{"q":-1}"#).is_err());
+    assert!(serde_json::from_str::<Q>(r#"{"q":"x"}"#).is_err());
+}
+
+#[test]
+fn winstons_as_numbers_serialize() {
+    #[derive(serde::Serialize, Deserialize, Debug, PartialEq)]
+    struct Q {
+        #[serde(with = "winstons_as_numbers")]
+        q: Winstons,
+    }
+
+    let q = Q { q: Winstons::from(u64::MAX) };
+    assert_eq!(serde_json::to_string(&q).unwrap(), format!(r#"{{"q":{}}}"#, u64::MAX));
+    let q = Q { q: Winstons::from(u64::MAX) + Winstons::from(1u32) };
+    assert_eq!(serde_json::to_string(&q).unwrap(), r#"{"q":"18446744073709551616"}"#);
+    assert_eq!(serde_json::from_str::<Q>(&serde_json::to_string(&q).unwrap()).unwrap(), q);
+}
+
+#[test]
+fn winstons_sub() {
+    let a = Winstons::from(7u32);
+    let b = Winstons::from(5u32);
+    assert_eq!(&a - &b, Winstons::from(2u32));
+    assert_eq!(&b - &a, Winstons::from(0u32));
+    assert_eq!(a.checked_sub(&b), Some(Winstons::from(2u32)));
+    assert_eq!(b.checked_sub(&a), None);
+    assert_eq!(a - b, Winstons::from(2u32));
+}
+
+#[test]
+fn winstons_mul() {
+    let a = Winstons::from(7u32);
+    assert_eq!(&a * 3, Winstons::from(21u32));
+    assert_eq!(a * u64::MAX, Winstons::from(7u128 * u64::MAX as u128));
+}
+
+#[test]
+fn from_str() {
+    assert_eq!("42".parse::<Winstons>().unwrap(), Winstons::from(42u32));
+    assert!("-42".parse::<Winstons>().is_err());
+
+    let a = "T22ykpEoUQerm0_8wSpiOE_2xUOGmG1lnf7niSiQlaU";
+    assert_eq!(a.parse::<Address>().unwrap(), Address::decode(a).unwrap());
+    let txh = "wbezNDNLwOf7qIYEtyHCjRs_kUafeiGF28REpip6DU8";
+    assert_eq!(txh.parse::<TxHash>().unwrap(), TxHash::decode(txh).unwrap());
+    let bh = "2serU-303rThvozelaLz67ftihCw7cJPAEF40SkPkfyvz92Z5gCKVfhadoVU3ZRg";
+    assert_eq!(bh.parse::<BlockHash>().unwrap(), BlockHash::decode(bh).unwrap());
+    assert!(txh.parse::<BlockHash>().is_err());
+}
+
+#[test]
+fn sign_offline() {
+    let w = Wallet::with_key_size(2048).unwrap();
+    let tx = TxBuilder::new(Anchor::Transaction(None))
+        .quantity(Winstons::from(1000u32)).target(fresh::address())
+        .add_tag(("App-Name", "arweaver"))
+        .reward_winstons(Winstons::from(42u32))
+        .sign(&w).unwrap();
+    assert_eq!(tx.reward, Winstons::from(42u32));
+    assert_eq!(tx.tags, Tags::from(vec![("App-Name", "arweaver")]));
+    assert!(tx.verify().unwrap());
+}
+
+#[test]
+fn sign_externally() {
+    let w = Wallet::with_key_size(2048).unwrap();
+    let txb = TxBuilder::new(Anchor::Transaction(None))
+        .data(Data::from(b"hello".to_vec()))
+        .reward_winstons(Winstons::from(42u32));
+
+    let bs = txb.signing_data(w.owner()).unwrap();
+    let mut s = openssl::sign::Signer::new_without_digest(w.key()).unwrap();
+    s.set_rsa_padding(openssl::rsa::Padding::PKCS1_PSS).unwrap();
+    s.update(&bs).unwrap();
+    let sig = Signature::new(s.sign_to_vec().unwrap()).unwrap();
+
+    let tx = txb.with_signature(w.owner().clone(), sig).unwrap();
+    assert!(tx.verify().unwrap());
+}
+
+#[test]
+fn wallet_from_jwk() {
+    let w = Wallet::from_jwk(include_str!("fixtures/wallet.json")).unwrap();
+    assert_eq!(w.address(), &Address::decode("7GGmzRm6m__kN_uFqbBHaeysYg-txNVqQ5f5qWJxHZc").unwrap());
+
+    let tx = TxBuilder::new(Anchor::Transaction(None))
+        .reward_winstons(Winstons::from(42u32)).sign(&w).unwrap();
+    assert!(tx.verify().unwrap());
+
+    assert!(Wallet::from_jwk("{}").is_err());
+    assert!(Wallet::from_jwk(&include_str!("fixtures/wallet.json").replace("AQAB", "Aw")).is_err());
+}
+
+#[test]
+fn wallet_to_jwk() {
+    let jwk = include_str!("fixtures/wallet.json");
+    let w = Wallet::from_jwk(jwk).unwrap();
+    let v0: serde_json::Value = serde_json::from_str(jwk).unwrap();
+    let v1: serde_json::Value = serde_json::from_str(&w.to_jwk().unwrap()).unwrap();
+    assert_eq!(v0, v1);
+
+    let w0 = Wallet::with_key_size(2048).unwrap();
+    let w1 = Wallet::from_jwk(&w0.to_jwk().unwrap()).unwrap();
+    assert_eq!(w0.address(), w1.address());
+}
+
+#[test]
+fn sign_message() {
+    let w = Wallet::from_jwk(include_str!("fixtures/wallet.json")).unwrap();
+    let sig = w.sign_message("hello").unwrap();
+    assert!(w.owner().verify_message("hello", &sig).unwrap());
+    assert!(!w.owner().verify_message("hello!", &sig).unwrap());
+}
+
+#[test]
+fn tx_v2() {
+    let tx: Tx = serde_json::from_str(include_str!("fixtures/tx_v2.json")).unwrap();
+    assert_eq!(tx.format, 2);
+    assert_eq!(tx.data_size, 14);
+    assert!(tx.verify().unwrap());
+    let v: serde_json::Value = serde_json::from_str(include_str!("fixtures/tx_v2.json")).unwrap();
+    assert_eq!(tx.signature.to_string(), v["signature"].as_str().unwrap());
+    assert_eq!(tx.signature.as_bytes().len(), 512);
+
+    let mut tx1: Tx = serde_json::from_str(include_str!("fixtures/tx_v2.json")).unwrap();
+    tx1.id = TxHash::decode("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA").unwrap();
+    assert!(!tx1.verify_id().unwrap());
+    assert!(!tx1.verify().unwrap());
+
+    let w = Wallet::from_jwk(include_str!("fixtures/wallet.json")).unwrap();
+    let target = tx.target().unwrap().clone();
+    let txb = TxBuilder::new(tx.anchor).format(2).target(target)
+        .quantity(tx.quantity).reward_winstons(tx.reward)
+        .data(tx.data).tags(tx.tags);
+    let dh = "1L6PSmLNBxkAugPvZp7YBQXtYJOb_NhflQ6dn6zdjAhOzH5VL-kdLomT5BvgJp4s";
+    assert_eq!(txb.signing_data(w.owner()).unwrap(), base64::decode_config(dh, base64::URL_SAFE_NO_PAD).unwrap());
+
+    let tx = txb.sign(&w).unwrap();
+    assert_eq!(tx.data_root().unwrap(), &DataRoot::decode("s81JxNDb3Mc7FQyZGeLHp_-IhpkeaaM_XvzSdL_Fg9c").unwrap());
+    assert!(tx.verify().unwrap());
+}
+
+#[test]
+fn data_root() {
+    fn pattern(n: usize) -> Data { Data::from((0..n).map(|i| (i % 251) as u8).collect::<Vec<u8>>()) }
+    let vs = vec![
+        (1, "Ht_yZhXGBDUZfLv4OD6we7FkrjcpDpGPwQZEZgEJVfk"),
+        (1000, "OBN0lHZnrFrskv9s1HKU7VRRDXrzQ3sOgMniX5cGavM"),
+        (262144, "gty7KB2baLFp7OGxuV2wBeX3NippS1tNVlMOZryIq5o"),
+        (263144, "bFtxR6l6BRJt4Y7utAH1mGDg4Ps969jw17_NfjGzB_Q"),
+        (294912, "qk73Qz9Ymp1tr4dzJw_hTDS1VoVwBeLLNwkytN1sBYY"),
+        (524288, "H2bNmvftzAlIQXVYCetFsJ9f0uoC4C2FbfivZTRSyqI"),
+        (798777, "D2x_RLK2sSdmz7vBm3qvENY5eI2wE03uBWC0HHpYyBw"),
+    ];
+    for (n, r) in vs {
+        assert_eq!(DataRoot::of(&pattern(n)).unwrap(), Some(DataRoot::decode(r).unwrap()));
+    }
+    assert_eq!(DataRoot::of(&Data::from(vec![])).unwrap(), None);
+}
+
+#[test]
+fn block_header() {
+    let b: Block = serde_json::from_str(r#"{
+        "indep_hash": "pXJ_5CPNqY7H1UvoIiBKnLUtLpMz9Gdd1k42TIUIRAZcUkr8mT6FSZV7Lg8HpMjh",
+        "previous_block": "",
+        "height": 0,
+        "txs": [],
+        "timestamp": 1528500720,
+        "nonce": "AAAA",
+        "diff": "34",
+        "cumulative_diff": 34,
+        "reward_pool": "1000",
+        "reward_addr": "unclaimed",
+        "block_size": 0,
+        "weave_size": 0,
+        "tx_root": ""
+    }"#).unwrap();
+    assert_eq!(b.nonce.as_ref().unwrap().as_bytes(), &[0, 0, 0]);
+    assert_eq!(b.diff, Some(Difficulty::from(34)));
+    assert_eq!(b.cumulative_diff, b.diff);
+    assert_eq!(b.reward_pool, Some(Winstons::from(1000u64)));
+    assert_eq!(b.reward_addr, None);
+    assert_eq!(b.weave_size, Some(0));
+    assert_eq!(b.tx_root(), None);
+    assert_eq!(b.timestamp_secs(), 1528500720);
+    let b1: Block = serde_json::from_str(&serde_json::to_string(&b).unwrap()).unwrap();
+    assert_eq!(b, b1);
+
+    let b: Block = serde_json::from_str(r#"{
+        "indep_hash": "pXJ_5CPNqY7H1UvoIiBKnLUtLpMz9Gdd1k42TIUIRAZcUkr8mT6FSZV7Lg8HpMjh",
+        "previous_block": "",
+        "height": 0,
+        "txs": [],
+        "timestamp": 1528500720,
+        "reward_addr": "7GGmzRm6m__kN_uFqbBHaeysYg-txNVqQ5f5qWJxHZc"
+    }"#).unwrap();
+    assert_eq!(b.reward_addr, Some(Address::decode("7GGmzRm6m__kN_uFqbBHaeysYg-txNVqQ5f5qWJxHZc").unwrap()));
+    assert_eq!(b.diff, None);
+}
+
+#[test]
+fn block_reward_addr_sentinels() {
+    let block = |reward_addr: &str| serde_json::from_str::<Block>(&format!(r#"{{
+        "indep_hash": "pXJ_5CPNqY7H1UvoIiBKnLUtLpMz9Gdd1k42TIUIRAZcUkr8mT6FSZV7Lg8HpMjh",
+        "previous_block": "",
+        "height": 0,
+        "txs": [],
+        "timestamp": 1528500720,
+        "reward_addr": "{}"
+    }}"#, reward_addr));
+    assert_eq!(block("").unwrap().reward_addr, None);
+    assert_eq!(block("unclaimed").unwrap().reward_addr, None);
+    assert!(block("AAAA").is_err());
+}
+
+struct FakeGateway { price: Winstons }
+
+impl Gateway for FakeGateway {
+    fn price(&self, _: Option<&Address>, size: usize) -> Result<Winstons, Error> {
+        Ok(&self.price * (size as u64 + 1))
+    }
+
+    fn tx_anchor(&self) -> Result<Anchor, Error> {
+        Ok(Anchor::Transaction(None))
+    }
+
+    fn balance(&self, _: &Address) -> Result<Winstons, Error> {
+        Ok(Winstons::from(100u64))
+    }
+}
+
+#[test]
+fn reward_from_gateway() {
+    let g = FakeGateway { price: Winstons::from(10u64) };
+    let w = Wallet::from_jwk(include_str!("fixtures/wallet.json")).unwrap();
+    let tx = TxBuilder::new(Anchor::latest(&g).unwrap())
+        .data(Data::from(vec![1, 2, 3]))
+        .reward(&g).unwrap()
+        .sign(&w).unwrap();
+    assert_eq!(tx.reward, Winstons::from(40u64));
+    assert!(tx.verify().unwrap());
+
+    let txb = TxBuilder::new(Anchor::Transaction(None))
+        .data(Data::from(vec![1, 2, 3])).add_tag(("Content-Type", "text/plain"));
+    assert_eq!(txb.reward(&g).unwrap().total_cost().unwrap(), Winstons::from(10u64 * (3 + 12 + 10 + 1)));
+
+    let c = Client::with_url(serve_routes(vec![
+        ("/price/27", Reply::new("200 OK", "270")), ("/price/27", Reply::new("200 OK", "270")),
+    ])).unwrap();
+    let txb = TxBuilder::new(Anchor::Transaction(None)).data(Data::from(b"hello".to_vec())).add_tag(("Content-Type", "text/plain"));
+    assert_eq!(c.price_for_builder(&txb).unwrap(), Winstons::from(270u64));
+    assert_eq!(txb.reward(&c).unwrap().total_cost().unwrap(), Winstons::from(270u64));
+}
+
+#[test]
+fn height_range() {
+    let hs = Height::range(Height::from(3), Height::from(5)).collect::<Vec<_>>();
+    assert_eq!(hs, vec![Height::from(3), Height::from(4), Height::from(5)]);
+    assert_eq!(Height::range(Height::from(5), Height::from(3)).count(), 0);
+    assert_eq!(Height::from(0).prev(), Height::from(0));
+    assert_eq!(Height::from(3).next(), Height::from(4));
+    assert_eq!(Height::from(u64::MAX).next(), Height::from(u64::MAX));
+}
+
+#[test]
+fn height_add() {
+    let max = Height::from(u64::MAX);
+    assert_eq!(Height::from(3).checked_add(Height::from(4)), Some(Height::from(7)));
+    assert_eq!(max.checked_add(Height::from(1)), None);
+    assert_eq!(max.saturating_add(Height::from(1)), max);
+    assert_eq!(max + Height::from(1), max);
+    assert_eq!(Height::from(3) + Height::from(4), Height::from(7));
+}
+
+#[test]
+fn tag_lookup() {
+    let ts = Tags::from(vec![("Content-Type", "text/plain"), ("App-Name", "arweaver")]);
+    assert_eq!(ts.get("App-Name").unwrap().to_string_lossy(), "arweaver");
+    assert_eq!(ts.get("Content-Type").unwrap().as_bytes(), b"text/plain");
+    assert!(ts.contains("Content-Type"));
+    assert!(!ts.contains("content-type"));
+    assert_eq!(ts.get("Missing"), None);
+    assert_eq!(ts.iter().map(|t| t.name().to_string_lossy()).collect::<Vec<_>>(), vec!["Content-Type", "App-Name"]);
+}
+
+#[test]
+fn data_file() {
+    let p = std::env::temp_dir().join(format!("arweaver-{}", std::process::id()));
+    let d = Data::from(b"hello".to_vec());
+    d.write_to_file(&p).unwrap();
+    assert_eq!(Data::from_file(&p).unwrap(), d);
+    std::fs::remove_file(&p).unwrap();
+    assert!(matches!(Data::from_file(&p), Err(Error::IoError(_))));
+}
+
+#[test]
+fn std_error() {
+    use std::error::Error as _;
+    let e: Box<dyn std::error::Error> = Box::new(Data::from_file("/nonexistent").unwrap_err());
+    assert!(e.source().unwrap().is::<std::io::Error>());
+    assert!(Error::invalid_value("thing", "msg").source().is_none());
+}
+
+#[test]
+fn total_cost() {
+    let g = FakeGateway { price: Winstons::from(10u64) };
+    let w = Wallet::from_jwk(include_str!("fixtures/wallet.json")).unwrap();
+    let txb = TxBuilder::new(Anchor::Transaction(None)).quantity(Winstons::from(50u64));
+    assert!(txb.total_cost().is_err());
+
+    let txb = txb.reward(&g).unwrap();
+    assert_eq!(txb.total_cost().unwrap(), Winstons::from(60u64));
+    assert!(txb.affordable_by(&g, &w).unwrap());
+
+    let txb = txb.quantity(Winstons::from(91u64));
+    assert!(!txb.affordable_by(&g, &w).unwrap());
+}
+
+#[test]
+fn content_type() {
+    assert_eq!(Data::guess_content_type("index.html"), "text/html");
+    assert_eq!(Data::guess_content_type("photo.JPG"), "image/jpeg");
+    assert_eq!(Data::guess_content_type("data.json"), "application/json");
+    assert_eq!(Data::guess_content_type("README"), "application/octet-stream");
+
+    let w = Wallet::from_jwk(include_str!("fixtures/wallet.json")).unwrap();
+    let tx = TxBuilder::new(Anchor::Transaction(None))
+        .data(Data::from(b"hello".to_vec()))
+        .add_tag(("App-Name", "arweaver"))
+        .content_type("text/html")
+        .content_type("text/plain")
+        .reward_winstons(Winstons::from(42u32))
+        .sign(&w).unwrap();
+    assert_eq!(tx.tags, Tags::from(vec![("App-Name", "arweaver"), ("Content-Type", "text/plain")]));
+}
+
+#[derive(Clone)]
+struct Reply {
+    status: String,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl Reply {
+    fn new<S: AsRef<str>, B: AsRef<str>>(status: S, body: B) -> Self {
+        Reply { status: status.as_ref().to_string(), headers: Vec::new(), body: body.as_ref().to_string() }
+    }
+
+    fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+// answers n connections, each with the reply for the raw request
+fn listen<F: FnMut(&str) -> Reply + Send + 'static>(n: usize, mut reply: F) -> String {
+    use std::io::{Read, Write};
+    let l = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", l.local_addr().unwrap());
+    std::thread::spawn(move || {
+        for _ in 0..n {
+            let (mut s, _) = l.accept().unwrap();
+            let mut buf = [0; 4096];
+            let k = s.read(&mut buf).unwrap();
+            let r = reply(&String::from_utf8_lossy(&buf[..k]));
+            let hs = r.headers.iter().map(|(k, v)| format!("{}: {}\r\n", k, v)).collect::<String>();
+            let rsp = format!("HTTP/1.1 {}\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                r.status, hs, r.body.len(), r.body);
+            s.write_all(rsp.as_bytes()).unwrap();
+        }
+    });
+    url
+}
+
+fn serve(responses: Vec<(&str, &str)>) -> String {
+    let mut rs = responses.into_iter().map(|(s, b)| Reply::new(s, b)).collect::<Vec<_>>().into_iter();
+    listen(rs.len(), move |_| rs.next().unwrap())
+}
+
+// answers one request per route, picking the reply by request path
+fn serve_routes(routes: Vec<(&str, Reply)>) -> String {
+    let routes = routes.into_iter().map(|(p, r)| (p.to_string(), r)).collect::<Vec<_>>();
+    listen(routes.len(), move |req| {
+        let path = req.split(' ').nth(1).unwrap_or_default();
+        routes.iter().find(|(p, _)| p == path).map(|(_, r)| r.clone())
+            .unwrap_or_else(|| Reply::new("404 Not Found", format!("no {}", path)))
+    })
+}
+
+fn serve_once<S: AsRef<str>, B: AsRef<str>>(status: S, body: B) -> String {
+    serve(vec![(status.as_ref(), body.as_ref())])
+}
+
+#[test]
+fn http_status() {
+    let url = serve_once("404 Not Found", "Not Found");
+    let c = Client::with_url(url).unwrap();
+    match c.info() {
+        Err(Error::HttpStatus { status, body, url, .. }) => {
+            assert_eq!(status, 404);
+            assert_eq!(body, "Not Found");
+            assert!(url.ends_with("/info"));
+        },
+        r => panic!("unexpected: {:?}", r.map(|_| ())),
+    }
+}
+
+#[test]
+fn data_stream() {
+    use std::io::Read;
+    let url = serve_once("200 OK", "hello");
+    let c = Client::with_url(url).unwrap();
+    let txh = TxHash::decode("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA").unwrap();
+    let mut s = String::new();
+    c.data_stream(&txh).unwrap().read_to_string(&mut s).unwrap();
+    assert_eq!(s, "hello");
+}
+
+#[test]
+fn byte_size() {
+    let txb = TxBuilder::new(Anchor::Transaction(None))
+        .data(Data::from(b"hello".to_vec()))
+        .add_tag(("Content-Type", "text/plain"));
+    assert_eq!(txb.byte_size(), 5 + 12 + 10);
+}
+
+#[test]
+fn info() {
+    let i: Info = serde_json::from_str(r#"{
+        "network": "arweave.N.1",
+        "version": 5,
+        "release": 43,
+        "height": 551511,
+        "current": "XIDpYbc3b5iuiqclSl_Hrx263Sd4zzmrNja1cvFlqNWUGuyymhhGZYI4WMsID1K3",
+        "blocks": 97375,
+        "peers": 64,
+        "queue_length": 0,
+        "node_state_latency": 18
+    }"#).unwrap();
+    assert_eq!(i.network.as_deref(), Some("arweave.N.1"));
+    assert_eq!(i.version, Some(5));
+    assert_eq!(i.release, Some(43));
+    assert_eq!(i.height, Height::from(551511));
+    assert_eq!(i.blocks, Some(97375));
+    assert_eq!(i.peers, Some(64));
+    assert_eq!(i.queue_length, Some(0));
+
+    let i: Info = serde_json::from_str(r#"{
+        "height": 12,
+        "current": "XIDpYbc3b5iuiqclSl_Hrx263Sd4zzmrNja1cvFlqNWUGuyymhhGZYI4WMsID1K3"
+    }"#).unwrap();
+    assert_eq!(i.height, Height::from(12));
+    assert_eq!((i.network, i.release, i.queue_length), (None, None, None));
+}
+
+#[test]
+fn collector() {
+    let w = Wallet::from_jwk(include_str!("fixtures/wallet.json")).unwrap();
+    let txb = TxBuilder::new(Anchor::Transaction(None))
+        .data(Data::from(b"hello".to_vec()))
+        .reward_winstons(Winstons::from(42u32));
+    let bs = txb.signing_data(w.owner()).unwrap();
+    let tx = txb.sign(&w).unwrap();
+
+    let mut c = Collector::default();
+    tx.squeeze(&mut c).unwrap();
+    assert_eq!(c.into_bytes(), bs);
+}
+
+#[test]
+fn expect_network() {
+    let url = serve_once("200 OK", concat!(
+        r#"{"network":"arweave.localnet","version":5,"release":43,"height":0,"#,
+        r#""current":"XIDpYbc3b5iuiqclSl_Hrx263Sd4zzmrNja1cvFlqNWUGuyymhhGZYI4WMsID1K3","#,
+        r#""blocks":1,"peers":0,"queue_length":0}"#));
+    let mut c = Client::with_url(url).unwrap();
+    c.expect_network("arweave.N.1");
+
+    let w = Wallet::from_jwk(include_str!("fixtures/wallet.json")).unwrap();
+    let tx = TxBuilder::new(Anchor::Transaction(None))
+        .reward_winstons(Winstons::from(42u32)).sign(&w).unwrap();
+    match c.submit(&tx) {
+        Err(e @ Error::NetworkMismatch { .. }) =>
+            assert_eq!(e.to_string(), "expected network arweave.N.1, gateway is on arweave.localnet"),
+        r => panic!("unexpected: {:?}", r),
+    }
+
+    let url = serve_once("200 OK",
+        r#"{"height":0,"current":"XIDpYbc3b5iuiqclSl_Hrx263Sd4zzmrNja1cvFlqNWUGuyymhhGZYI4WMsID1K3"}"#);
+    let mut c = Client::with_url(url).unwrap();
+    c.expect_network("arweave.N.1");
+    assert!(matches!(c.submit(&tx), Err(Error::NetworkMismatch { actual: None, .. })));
+}
+
+#[test]
+fn owner_from_modulus() {
+    let w = Wallet::from_jwk(include_str!("fixtures/wallet.json")).unwrap();
+    let jwk: serde_json::Value = serde_json::from_str(include_str!("fixtures/wallet.json")).unwrap();
+    let n = base64::decode_config(jwk["n"].as_str().unwrap(), base64::URL_SAFE_NO_PAD).unwrap();
+    let o = Owner::from_modulus(&n).unwrap();
+    assert_eq!(&o.address().unwrap(), w.address());
+    assert!(Owner::from_modulus([]).is_err());
+}
+
+#[test]
+fn tags_collection() {
+    let mut ts = Tags::new();
+    assert!(ts.is_empty());
+    ts.push(Tag::from(("a", "1")));
+    ts.push(Tag::from(("b", "2")));
+    ts.push(Tag::from(("a", "3")));
+    assert_eq!(ts.len(), 3);
+
+    ts.dedup_by_name();
+    assert_eq!(ts, Tags::from(vec![("b", "2"), ("a", "3")]));
+    assert_eq!(ts, Tags::from(vec![(Name::from("b"), Value::from("2")), (Name::from("a"), Value::from("3"))]));
+    assert_eq!((&ts).into_iter().count(), 2);
+    assert_eq!(ts.into_iter().map(|t| t.value().to_string_lossy().into_owned()).collect::<Vec<_>>(), vec!["2", "3"]);
+}
+
+#[test]
+fn data_as_str() {
+    let d = Data::from(b"hello".to_vec());
+    assert_eq!(d.as_str().unwrap(), "hello");
+    assert_eq!(d.to_string_lossy(), "hello");
+
+    let d = Data::from(vec![0x68, 0xff]);
+    assert!(d.as_str().is_err());
+    assert_eq!(d.to_string_lossy(), "h\u{fffd}");
+}
+
+#[test]
+fn data_str() {
+    assert_eq!(Data::from("hello"), Data::from(b"hello".to_vec()));
+    assert_eq!(Data::from(String::from("hello")), Data::from(b"hello".to_vec()));
+
+    let w = Wallet::from_jwk(include_str!("fixtures/wallet.json")).unwrap();
+    let tx = TxBuilder::new(Anchor::Transaction(None))
+        .data_str("hello")
+        .reward_winstons(Winstons::from(42u32))
+        .sign(&w).unwrap();
+    assert_eq!(tx.data.as_str().unwrap(), "hello");
+    assert_eq!(tx.tags, Tags::from(vec![("Content-Type", "text/plain")]));
+
+    let tx = TxBuilder::new(Anchor::Transaction(None))
+        .content_type("text/markdown")
+        .data_str("# hello")
+        .reward_winstons(Winstons::from(42u32))
+        .sign(&w).unwrap();
+    assert_eq!(tx.tags, Tags::from(vec![("Content-Type", "text/markdown")]));
+}
+
+#[test]
+fn pending() {
+    let c = Client::with_url(serve_once("200 OK", "[]")).unwrap();
+    assert!(c.pending().unwrap().is_empty());
+
+    let c = Client::with_url(serve_once("200 OK", r#"["CCH2h2MzMP7WMh0Xf3GYL7zZDbU7E4CZPJWngp1qmDc"]"#)).unwrap();
+    assert_eq!(c.pending().unwrap(), vec![TxHash::decode("CCH2h2MzMP7WMh0Xf3GYL7zZDbU7E4CZPJWngp1qmDc").unwrap()]);
+}
+
+#[test]
+fn owner_round_trip() {
+    let o = Owner::from_modulus([0, 0xab, 0xcd]).unwrap();
+    let s = serde_json::to_string(&o).unwrap();
+    assert_eq!(s, r#""AKvN""#);
+    let o1: Owner = serde_json::from_str(&s).unwrap();
+    assert_eq!(o, o1);
+    assert_eq!(serde_json::to_string(&o1).unwrap(), s);
+
+    let v: serde_json::Value = serde_json::from_str(include_str!("fixtures/tx_v2.json")).unwrap();
+    let tx: Tx = serde_json::from_str(include_str!("fixtures/tx_v2.json")).unwrap();
+    assert_eq!(serde_json::to_value(&tx.owner).unwrap(), v["owner"]);
+}
+
+#[test]
+fn post_chunk() {
+    let d = Data::from("hello");
+    let c = Chunk {
+        data_root: DataRoot::of(&d).unwrap().unwrap(),
+        data_size: 5,
+        offset: 4,
+        data_path: DataPath::new([1, 2, 3]),
+        chunk: d,
+    };
+    let v = serde_json::to_value(&c).unwrap();
+    assert_eq!(v["data_size"], "5");
+    assert_eq!(v["offset"], "4");
+    assert_eq!(v["data_path"], "AQID");
+    assert_eq!(v["chunk"], "aGVsbG8");
+    assert_eq!(serde_json::from_value::<Chunk>(v).unwrap(), c);
+
+    let client = Client::with_url(serve_once("200 OK", "")).unwrap();
+    client.post_chunk(&c).unwrap();
+
+    let client = Client::with_url(serve_once("400 Bad Request", r#"{"error":"invalid_proof"}"#)).unwrap();
+    assert!(matches!(client.post_chunk(&c), Err(Error::HttpStatus { status: 400, .. })));
+}
+
+#[test]
+fn data_tree() {
+    fn pattern(n: usize) -> Vec<u8> { (0..n).map(|i| (i % 251) as u8).collect() }
+    let vs = vec![
+        (1000, "OBN0lHZnrFrskv9s1HKU7VRRDXrzQ3sOgMniX5cGavM", vec![999], "ZTWssrqSGHEj9UUVPbUwa3yxuDhqmZOiY9vHs0dTNr0"),
+        (262144, "gty7KB2baLFp7OGxuV2wBeX3NippS1tNVlMOZryIq5o", vec![262143], "Nw5wpUN2rxrpNR80UUhXnqarJFyOqvr1-XbrPdk5x_Y"),
+        (524288, "H2bNmvftzAlIQXVYCetFsJ9f0uoC4C2FbfivZTRSyqI", vec![262143, 524287], "nHiF6j7rb26Hf5DQQBdne50raJfCXZcDi5moKHkvj9w"),
+        (798777, "D2x_RLK2sSdmz7vBm3qvENY5eI2wE03uBWC0HHpYyBw", vec![262143, 524287, 661532, 798776], "c_N0uNcgFEQpmzliyCOXe9n10P6vNPuhHj0Xfkb27to"),
+    ];
+    for (n, r, offsets, proofs) in vs {
+        let data = pattern(n);
+        let t = DataTree::from_bytes(&data);
+        assert_eq!(t.root(), &DataRoot::decode(r).unwrap());
+        assert_eq!(t.size(), n);
+        assert_eq!(t.len(), offsets.len());
+
+        let cs = (0..t.len()).map(|i| t.chunk(&data, i).unwrap()).collect::<Vec<_>>();
+        assert_eq!(cs.iter().map(|c| c.offset).collect::<Vec<_>>(), offsets);
+        assert_eq!(cs.iter().map(|c| c.chunk.len()).sum::<usize>(), n);
+
+        let ps = (0..t.len()).flat_map(|i| t.proof(i).unwrap().as_bytes().to_vec()).collect::<Vec<_>>();
+        assert_eq!(base64::encode_config(&openssl::sha::sha256(&ps), base64::URL_SAFE_NO_PAD), proofs);
+        assert!(t.proof(t.len()).is_none());
+    }
+    assert!(DataTree::from_bytes(&[]).is_empty());
+}
+
+#[test]
+fn redirect_policy() {
+    let target = serve_once("200 OK", "[]");
+    let location = format!("302 Found\r\nLocation: {}/tx/pending", target);
+    let c = Client::with_url(serve_once(location.as_str(), "")).unwrap();
+    assert!(c.pending().unwrap().is_empty());
+
+    let c = Client::with_url(serve_once(location, "")).unwrap()
+        .with_redirect_policy(RedirectPolicy::None).unwrap();
+    assert!(matches!(c.pending(), Err(Error::HttpStatus { status: 302, .. })));
+}
+
+#[test]
+fn winstons_assign() {
+    let mut w = Winstons::zero();
+    assert!(w.is_zero());
+    w += Winstons::from(5u32);
+    w += &Winstons::from(2u32);
+    assert_eq!(w, Winstons::from(7u32));
+    w -= Winstons::from(3u32);
+    assert_eq!(w, Winstons::from(4u32));
+    w -= &Winstons::from(10u32);
+    assert!(w.is_zero());
+}
+
+#[test]
+fn lenient_base64() {
+    let txh = TxHash::decode("CCH2h2MzMP7WMh0Xf3GYL7zZDbU7E4CZPJWngp1qmDc").unwrap();
+    assert_eq!(TxHash::decode("CCH2h2MzMP7WMh0Xf3GYL7zZDbU7E4CZPJWngp1qmDc=").unwrap(), txh);
+    assert_eq!(TxHash::decode("CCH2h2MzMP7WMh0Xf3GYL7zZDbU7E4CZPJWngp1qmDc").unwrap().encode(), txh.encode());
+
+    let a = Address::decode("7GGmzRm6m__kN_uFqbBHaeysYg-txNVqQ5f5qWJxHZc").unwrap();
+    assert_eq!(Address::decode("7GGmzRm6m//kN/uFqbBHaeysYg+txNVqQ5f5qWJxHZc=").unwrap(), a);
+    assert_eq!(Address::decode("7GGmzRm6m//kN/uFqbBHaeysYg+txNVqQ5f5qWJxHZc").unwrap().encode(), a.encode());
+    assert!(Address::decode("7GGmzRm6m__kN/uFqbBHaeysYg+txNVqQ5f5qWJxHZc").is_err());
+}
+
+#[test]
+fn query_by_tags() {
+    let c = Client::with_url(serve_once("200 OK", r#"{"data":{"transactions":{
+        "pageInfo":{"hasNextPage":true},
+        "edges":[
+            {"cursor":"c1","node":{"id":"CCH2h2MzMP7WMh0Xf3GYL7zZDbU7E4CZPJWngp1qmDc"}},
+            {"cursor":"c2","node":{"id":"wbezNDNLwOf7qIYEtyHCjRs_kUafeiGF28REpip6DU8"}}
+        ]}}}"#)).unwrap();
+    let p = c.query_by_tags(&[("App-Name", "arweaver")], None).unwrap();
+    assert_eq!(p.txs, vec![
+        TxHash::decode("CCH2h2MzMP7WMh0Xf3GYL7zZDbU7E4CZPJWngp1qmDc").unwrap(),
+        TxHash::decode("wbezNDNLwOf7qIYEtyHCjRs_kUafeiGF28REpip6DU8").unwrap(),
+    ]);
+    assert_eq!(p.cursor, Some("c2".to_string()));
+
+    let c = Client::with_url(serve_once("200 OK",
+        r#"{"data":{"transactions":{"pageInfo":{"hasNextPage":false},"edges":[]}}}"#)).unwrap();
+    let p = c.query_by_tags(&[("App-Name", "arweaver")], Some("c2".to_string())).unwrap();
+    assert_eq!(p, TxPage { txs: vec![], cursor: None });
+
+    let c = Client::with_url(serve_once("200 OK", r#"{"errors":[{"message":"bad query"}]}"#)).unwrap();
+    assert!(matches!(c.graphql("{", serde_json::json!({})), Err(Error::InvalidValue { .. })));
+}
+
+#[test]
+fn wallet_txs() {
+    let page = |txs: &[&str], next: bool| format!(
+        r#"{{"data":{{"transactions":{{"pageInfo":{{"hasNextPage":{}}},"edges":[{}]}}}}}}"#, next,
+        txs.iter().map(|t| format!(r#"{{"cursor":"{}","node":{{"id":"{}"}}}}"#, t, t)).collect::<Vec<_>>().join(","));
+    let (a, b, c) = (
+        "CCH2h2MzMP7WMh0Xf3GYL7zZDbU7E4CZPJWngp1qmDc",
+        "wbezNDNLwOf7qIYEtyHCjRs_kUafeiGF28REpip6DU8",
+        "T22ykpEoUQerm0_8wSpiOE_2xUOGmG1lnf7niSiQlaU",
+    );
+    let (p0, p1, p2) = (page(&[a], true), page(&[b], false), page(&[a, c], false));
+    let url = serve(vec![("200 OK", &p0), ("200 OK", &p1), ("200 OK", &p2)]);
+
+    let client = Client::with_url(url).unwrap();
+    let txs = client.wallet_txs(&fresh::address()).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(txs, vec![TxHash::decode(a).unwrap(), TxHash::decode(b).unwrap(), TxHash::decode(c).unwrap()]);
+}
+
+#[test]
+fn binary_tags_v1() {
+    // signed with fixtures/wallet.json, see v1_preimage in the integration tests for a node-made one
+    let json: serde_json::Value = serde_json::from_str(include_str!("fixtures/tx_v1_binary_tags.json")).unwrap();
+    let tx: Tx = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(tx.format, 1);
+    assert!(tx.tags.iter().any(|t| std::str::from_utf8(t.name().as_bytes()).is_err()));
+    assert!(tx.tags.iter().any(|t| std::str::from_utf8(t.value().as_bytes()).is_err()));
+    assert!(tx.verify().unwrap());
+
+    let mut c = Collector::new();
+    tx.squeeze(&mut c).unwrap();
+    let preimage = c.into_bytes();
+    assert_eq!(preimage, v1::preimage(&json));
+    assert!(v1::verify(&json, &preimage));
+
+    let w = Wallet::from_jwk(include_str!("fixtures/wallet.json")).unwrap();
+    let txb = TxBuilder::new(tx.anchor).data(tx.data).reward_winstons(tx.reward).tags(tx.tags);
+    assert_eq!(txb.signing_data(w.owner()).unwrap(), preimage);
+}
+
+#[test]
+fn address_from_public_key() {
+    let a = Address::decode("7GGmzRm6m__kN_uFqbBHaeysYg-txNVqQ5f5qWJxHZc").unwrap();
+    assert_eq!(Address::from_public_pem(include_str!("fixtures/wallet.pub.pem")).unwrap(), a);
+
+    let jwk: serde_json::Value = serde_json::from_str(include_str!("fixtures/wallet.json")).unwrap();
+    let public = serde_json::json!({ "kty": "RSA", "n": jwk["n"], "e": jwk["e"] }).to_string();
+    assert_eq!(Address::from_jwk_public(&public).unwrap(), a);
+    assert_eq!(Address::from_jwk_public(include_str!("fixtures/wallet.json")).unwrap(), a);
+
+    let wrong_exponent = serde_json::json!({ "kty": "RSA", "n": jwk["n"], "e": "Aw" }).to_string();
+    assert!(Address::from_jwk_public(&wrong_exponent).is_err());
+    assert!(Address::from_public_pem("not a key").is_err());
+}
+
+fn block_json(indep: &str, height: u64) -> String {
+    format!(r#"{{"indep_hash":"{}","previous_block":"","height":{},"txs":[],"timestamp":1528500720}}"#, indep, height)
+}
+
+#[test]
+fn wait_for_new_block() {
+    let b0 = "pXJ_5CPNqY7H1UvoIiBKnLUtLpMz9Gdd1k42TIUIRAZcUkr8mT6FSZV7Lg8HpMjh";
+    let b1 = "2serU-303rThvozelaLz67ftihCw7cJPAEF40SkPkfyvz92Z5gCKVfhadoVU3ZRg";
+    let (r0, r1) = (block_json(b0, 0), block_json(b1, 1));
+    let c = Client::with_url(serve(vec![("200 OK", &r0), ("200 OK", &r0), ("200 OK", &r1)])).unwrap();
+    let b = c.wait_for_new_block(&BlockHash::decode(b0).unwrap(), std::time::Duration::from_millis(1)).unwrap();
+    assert_eq!(b.indep, BlockHash::decode(b1).unwrap());
+}
+
+#[test]
+fn blocks_since() {
+    let b0 = "pXJ_5CPNqY7H1UvoIiBKnLUtLpMz9Gdd1k42TIUIRAZcUkr8mT6FSZV7Lg8HpMjh";
+    let b1 = "2serU-303rThvozelaLz67ftihCw7cJPAEF40SkPkfyvz92Z5gCKVfhadoVU3ZRg";
+    let info = format!(r#"{{"network":"arweave.N.1","version":5,"release":43,"height":11,"current":"{}","blocks":12,"peers":0,"queue_length":0}}"#, b1);
+    let (r0, r1) = (block_json(b0, 10), block_json(b1, 11));
+    let c = Client::with_url(serve(vec![("200 OK", &info), ("200 OK", &r0), ("200 OK", &r1)])).unwrap();
+    let bs = c.blocks_since(Height::from(10)).into_iter().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(bs.iter().map(|b| b.height).collect::<Vec<_>>(), vec![Height::from(10), Height::from(11)]);
+}
+
+#[test]
+fn submit_returns_id() {
+    let w = Wallet::from_jwk(include_str!("fixtures/wallet.json")).unwrap();
+    let tx = TxBuilder::new(Anchor::Transaction(None))
+        .reward_winstons(Winstons::from(42u32)).sign(&w).unwrap();
+    let c = Client::with_url(serve_once("200 OK", "OK")).unwrap();
+    assert_eq!(c.submit(&tx).unwrap(), tx.id);
+}
+
+#[test]
+fn wallet_key_size() {
+    let w = Wallet::with_key_size(1024).unwrap();
+    assert_eq!(w.key().rsa().unwrap().size(), 128);
+    assert_eq!(w.key().rsa().unwrap().e().to_vec(), vec![1, 0, 1]);
+}
+
+#[test]
+fn address_new_length() {
+    assert!(Address::new([7u8; 32]).is_ok());
+    let e = Address::new([0xabu8; 31]).unwrap_err().to_string();
+    assert_eq!(e, "parsing address: invalid length (is 31, should be 32): abababababababab...");
+}
+
+fn serve_echo() -> String {
+    listen(1, |req| Reply::new("200 OK", req.to_lowercase()))
+}
+
+#[test]
+fn custom_headers() {
+    use std::io::Read;
+    let mut hs = HeaderMap::new();
+    hs.insert("x-api-key", "secret".parse().unwrap());
+    let c = Client::with_url(serve_echo()).unwrap().with_headers(hs).unwrap();
+    let txh = TxHash::decode("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA").unwrap();
+    let mut s = String::new();
+    c.data_stream(&txh).unwrap().read_to_string(&mut s).unwrap();
+    assert!(s.contains("x-api-key: secret"), "{}", s);
+}
+
+#[test]
+fn resolve_ar_url() {
+    let c = Client::with_url(serve_once("200 OK", "hello")).unwrap();
+    assert_eq!(c.resolve("ar://AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA").unwrap(), b"hello");
+
+    for u in &["https://AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA", "ar://", "ar://AAAA", "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA"] {
+        assert!(matches!(c.resolve(u), Err(Error::InvalidValue { .. })), "{}", u);
+    }
+}
+
+#[test]
+fn verify_all() {
+    let tx = || serde_json::from_str::<Tx>(include_str!("fixtures/tx_v2.json")).unwrap();
+    let mut bad = tx();
+    bad.id = TxHash::decode("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA").unwrap();
+    let txs = vec![tx(), bad, tx(), tx()];
+    let rs = Tx::verify_all(&txs).into_iter().map(Result::unwrap).collect::<Vec<_>>();
+    assert_eq!(rs, vec![true, false, true, true]);
+    assert!(Tx::verify_all(&[]).is_empty());
+}
+
+#[test]
+fn json_error() {
+    let url = serve_once("200 OK", r#"{"network": "arweave.N.1", "height": "x"}"#);
+    match Client::with_url(url).unwrap().info() {
+        Err(Error::JsonError(e)) => assert_eq!((e.line(), e.is_data()), (1, true)),
+        r => panic!("unexpected: {:?}", r.map(|_| ())),
+    }
+}
+
+#[test]
+fn price_cache() {
+    let a = fresh::address();
+    let url = serve_routes(vec![
+        ("/price/262144", Reply::new("200 OK", "100")),
+        (&format!("/price/262144/{}", a.encode()), Reply::new("200 OK", "200")),
+        ("/price/524288", Reply::new("200 OK", "300")),
+    ]);
+    let c = Client::with_url(url).unwrap().with_price_cache(std::time::Duration::from_secs(60));
+    assert_eq!(c.price(None::<&Address>, 1).unwrap(), Winstons::from(100u32));
+    assert_eq!(c.price(None::<&Address>, 1000).unwrap(), Winstons::from(100u32));
+    assert_eq!(c.price(Some(&a), 1000).unwrap(), Winstons::from(200u32));
+    assert_eq!(c.price(None::<&Address>, 300 * 1024).unwrap(), Winstons::from(300u32));
+    assert_eq!(c.price(Some(&a), 1).unwrap(), Winstons::from(200u32));
+
+    let url = serve(vec![("200 OK", "100"), ("200 OK", "200")]);
+    let c = Client::with_url(url).unwrap().with_price_cache(std::time::Duration::from_secs(0));
+    assert_eq!(c.price(None::<&Address>, 1).unwrap(), Winstons::from(100u32));
+    assert_eq!(c.price(None::<&Address>, 1).unwrap(), Winstons::from(200u32));
+}
+
+#[test]
+fn tx_tolerant_fields() {
+    let mut v: serde_json::Value = serde_json::from_str(include_str!("fixtures/tx_v1_binary_tags.json")).unwrap();
+    v.as_object_mut().unwrap().remove("format");
+    let tx: Tx = serde_json::from_value(v).unwrap();
+    assert_eq!(tx.format, 1);
+    assert_eq!(tx.data_root(), None);
+    assert_eq!(tx.data_size, 0);
+    assert!(tx.verify().unwrap());
+
+    let mut v: serde_json::Value = serde_json::from_str(include_str!("fixtures/tx_v2.json")).unwrap();
+    v["data_tree"] = serde_json::json!([]);
+    v["denomination"] = serde_json::json!("1");
+    let tx: Tx = serde_json::from_value(v).unwrap();
+    assert_eq!(tx.format, 2);
+    assert!(tx.verify().unwrap());
+}
+
+#[test]
+fn tags_from_iter() {
+    let mut m = std::collections::HashMap::new();
+    m.insert("App-Name".to_string(), "arweaver".to_string());
+    let mut ts: Tags = m.into_iter().collect();
+    assert_eq!(ts, Tags::from(vec![("App-Name", "arweaver")]));
+
+    ts.extend(vec![("Content-Type", "text/plain")]);
+    ts.extend(vec![(Name::from("a"), Value::from("b"))]);
+    assert_eq!(ts, Tags::from(vec![("App-Name", "arweaver"), ("Content-Type", "text/plain"), ("a", "b")]));
+    assert_eq!(ts.iter().cloned().collect::<Tags>(), ts);
+}
+
+#[test]
+fn block_index() {
+    let url = serve_once("200 OK", r#"[
+        {"tx_root": "", "weave_size": "1024", "hash": "pXJ_5CPNqY7H1UvoIiBKnLUtLpMz9Gdd1k42TIUIRAZcUkr8mT6FSZV7Lg8HpMjh"},
+        {"tx_root": "P_UI7i6Cj3s046yuDMDEhvRTpauP3XE5LsVSoOdd3wI", "weave_size": "0", "hash": "2serU-303rThvozelaLz67ftihCw7cJPAEF40SkPkfyvz92Z5gCKVfhadoVU3ZRg"}
+    ]"#);
+    let bi = Client::with_url(url).unwrap().block_index().unwrap();
+    assert_eq!(bi.len(), 2);
+    assert_eq!(bi[0].weave_size, 1024);
+    assert_eq!(bi[0].tx_root(), None);
+    assert!(bi[1].tx_root().is_some());
+    assert_eq!(bi[1].hash, BlockHash::decode("2serU-303rThvozelaLz67ftihCw7cJPAEF40SkPkfyvz92Z5gCKVfhadoVU3ZRg").unwrap());
+}
+
+#[test]
+fn base_path_prefix() {
+    use std::io::Read;
+    let txh = TxHash::decode("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA").unwrap();
+    for prefix in &["/arweave", "/arweave/"] {
+        let c = Client::with_url(format!("{}{}", serve_echo(), prefix)).unwrap();
+        let mut s = String::new();
+        c.data_stream(&txh).unwrap().read_to_string(&mut s).unwrap();
+        assert!(s.starts_with("get /arweave/aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa http/1.1"), "{}", s);
+    }
+
+    let url: Url = format!("{}/arweave", serve_echo()).parse().unwrap();
+    let c = Client::with_gateways(vec![url]).unwrap();
+    let mut s = String::new();
+    c.data_stream(&txh).unwrap().read_to_string(&mut s).unwrap();
+    assert!(s.starts_with("get /arweave/"), "{}", s);
+}
+
+#[test]
+fn validate_tx() {
+    let tx = || serde_json::from_str::<Tx>(include_str!("fixtures/tx_v2.json")).unwrap();
+    tx().validate().unwrap();
+    serde_json::from_str::<Tx>(include_str!("fixtures/tx_v1_binary_tags.json")).unwrap().validate().unwrap();
+
+    let msg = |tx: Tx| match tx.validate() {
+        Err(Error::InvalidValue { msg, .. }) => msg,
+        r => panic!("unexpected: {:?}", r),
+    };
+
+    let mut t = tx();
+    t.id = TxHash::decode("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA").unwrap();
+    assert_eq!(msg(t), "id does not match the signature");
+
+    let mut t = tx();
+    t.data = Data::from(vec![b'x'; t.data.len()]);
+    assert_eq!(msg(t), "data root does not match the data");
+
+    let mut t = tx();
+    t.data = Data::from(b"x".to_vec());
+    assert_eq!(msg(t), "data size does not match the data");
+
+    let mut t = tx();
+    t.reward = Winstons::from(1u32);
+    assert_eq!(msg(t), "signature does not verify");
+
+    let w = Wallet::with_key_size(2048).unwrap();
+    let t = TxBuilder::new(Anchor::Transaction(None)).target(w.address().clone())
+        .reward_winstons(Winstons::from(42u32)).sign(&w).unwrap();
+    assert_eq!(msg(t), "target is the owner's address");
+}
+
+#[test]
+fn wallet_pem() {
+    let w = Wallet::from_pem(include_bytes!("fixtures/wallet.pem")).unwrap();
+    assert_eq!(w.address(), Wallet::from_jwk(include_str!("fixtures/wallet.json")).unwrap().address());
+
+    let w1 = Wallet::from_pem(&w.to_pem().unwrap()).unwrap();
+    assert_eq!(w.address(), w1.address());
+
+    let pkcs1 = w.key().rsa().unwrap().private_key_to_pem().unwrap();
+    assert_eq!(Wallet::from_pem(&pkcs1).unwrap().address(), w.address());
+
+    let e3 = openssl::rsa::Rsa::generate_with_e(1024, &openssl::bn::BigNum::from_u32(3).unwrap()).unwrap();
+    assert!(Wallet::from_pem(&e3.private_key_to_pem().unwrap()).is_err());
+    assert!(Wallet::from_pem(include_bytes!("fixtures/wallet.pub.pem")).is_err());
+}
+
+#[test]
+fn wallet_debug() {
+    let w = Wallet::from_jwk(include_str!("fixtures/wallet.json")).unwrap();
+    let s = format!("{:?}", w);
+    assert!(s.contains(&w.address().encode()));
+    assert!(s.contains("<redacted private key>"));
+
+    let jwk: serde_json::Value = serde_json::from_str(include_str!("fixtures/wallet.json")).unwrap();
+    for k in &["d", "p", "q", "dp", "dq", "qi"] {
+        assert!(!s.contains(jwk[k].as_str().unwrap()));
+    }
+}
+
+#[test]
+fn balance_and_price() {
+    let a = fresh::address();
+    let url = serve_routes(vec![
+        (&format!("/wallet/{}/balance", a.encode()), Reply::new("200 OK", "1000")),
+        ("/price/10", Reply::new("200 OK", "7")),
+    ]);
+
+    let c = Client::with_url(url).unwrap();
+    let (b, p) = c.balance_and_price(&a, 10).unwrap();
+    assert_eq!(b, Winstons::from(1000u32));
+    assert_eq!(p, Winstons::from(7u32));
+}
+
+#[test]
+fn balance_errors() {
+    let a = fresh::address();
+    let url = serve(vec![("500 Internal Server Error", "<html>oops</html>"), ("500 Internal Server Error", "")]);
+    let c = Client::with_url(url).unwrap();
+    assert!(matches!(c.balance(&a), Err(Error::HttpStatus { status: 500, .. })));
+    assert!(matches!(c.price(Some(&a), 0), Err(Error::HttpStatus { status: 500, .. })));
+
+    let c = Client::with_url(serve_once("200 OK", "")).unwrap();
+    match c.balance(&a) {
+        Err(Error::InvalidValue { msg, .. }) => assert_eq!(msg, "empty value"),
+        r => panic!("unexpected: {:?}", r),
+    }
+}
+
+#[test]
+fn get_raw() {
+    let c = Client::with_url(format!("{}/prefix", serve_echo())).unwrap();
+    assert!(c.get_raw("info").unwrap().starts_with("get /prefix/info http/1.1"));
+}
+
+#[test]
+fn inline_data_limit() {
+    let w = Wallet::from_jwk(include_str!("fixtures/wallet.json")).unwrap();
+    let txb = || TxBuilder::new(Anchor::Transaction(None))
+        .data(Data::from(b"hello".to_vec())).reward_winstons(Winstons::from(42u32))
+        .max_inline_size(4);
+    assert!(matches!(txb().sign(&w), Err(Error::InvalidValue { .. })));
+    assert!(txb().format(2).sign(&w).unwrap().verify().unwrap());
+    assert!(txb().max_inline_size(5).sign(&w).unwrap().verify().unwrap());
+}
+
+#[test]
+fn clone_tx() {
+    let tx: Tx = serde_json::from_str(include_str!("fixtures/tx_v2.json")).unwrap();
+    let tx1 = tx.clone();
+    assert_eq!(tx, tx1);
+    assert!(tx1.verify().unwrap());
+    assert_eq!(tx.anchor.clone(), tx.anchor);
+}
+
+#[test]
+fn winstons_flexible() {
+    #[derive(Deserialize, serde::Serialize)]
+    struct Q {
+        #[serde(with = "winstons_flexible")]
+        q: Winstons,
+    }
+
+    let q: Q = serde_json::from_str(r#"{"q":1234567}"#).unwrap();
+    assert_eq!(q.q, Winstons::from(1234567u64));
+    let q: Q = serde_json::from_str(r#"{"q":"123456789012345678901234567890"}"#).unwrap();
+    assert_eq!(q.q, Winstons::decode("123456789012345678901234567890").unwrap());
+    assert_eq!(serde_json::to_string(&q).unwrap(), r#"{"q":"123456789012345678901234567890"}"#);
+    assert!(serde_json::from_str::<Q>(r#"{"q":-1}"#).is_err());
+    assert!(serde_json::from_str::<Q>(r#"{"q":"x"}"#).is_err());
+    assert!(serde_json::from_str::<Q>(r#"{"q":1.5}"#).is_err());
+
+    let mut v: serde_json::Value = serde_json::from_str(include_str!("fixtures/tx_v2.json")).unwrap();
+    let reward: u64 = v["reward"].as_str().unwrap().parse().unwrap();
+    v["reward"] = serde_json::json!(reward);
+    let tx: Tx = serde_json::from_value(v).unwrap();
+    assert!(tx.verify().unwrap());
+}
+
+#[test]
+fn weave_size_and_cost() {
+    let b0 = "pXJ_5CPNqY7H1UvoIiBKnLUtLpMz9Gdd1k42TIUIRAZcUkr8mT6FSZV7Lg8HpMjh";
+    let with_size = block_json(b0, 0).replace(r#""txs":[]"#, r#""txs":[],"weave_size":"4096""#);
+    let url = serve(vec![("200 OK", &with_size), ("200 OK", &block_json(b0, 0)), ("200 OK", "1234")]);
+    let c = Client::with_url(url).unwrap();
+    assert_eq!(c.weave_size().unwrap(), 4096);
+    assert!(matches!(c.weave_size(), Err(Error::ValueNotPresent { .. })));
+    assert_eq!(c.estimate_cost(100).unwrap(), Winstons::from(1234u32));
+}
+
+#[test]
+fn winstons_cmp_u64() {
+    let w = Winstons::from(1000u32);
+    assert!(w == 1000u64);
+    assert!(w != 999u64);
+    assert!(w > 999u64);
+    assert!(w <= 1000u64);
+    assert!(w < 1001u64);
+    assert!(Winstons::from(u64::MAX) + Winstons::from(1u32) > u64::MAX);
+}
+
+type Posts = std::sync::Arc<std::sync::Mutex<Vec<(String, Vec<u8>)>>>;
+
+struct MockBackend {
+    posts: Posts,
+}
+
+impl HttpBackend for MockBackend {
+    fn get(&self, url: &Url) -> Result<(u16, Vec<u8>), Error> {
+        match url.path() {
+            "/prefix/price/0" => Ok((200, b"42".to_vec())),
+            p => Ok((404, format!("no {}", p).into_bytes())),
+        }
+    }
+
+    fn post(&self, url: &Url, body: &[u8]) -> Result<(u16, Vec<u8>), Error> {
+        self.posts.lock().unwrap().push((url.path().to_string(), body.to_vec()));
+        match url.path() {
+            "/prefix/graphql" => Ok((200, br#"{"data":{"transactions":{"pageInfo":{"hasNextPage":false},"edges":[]}}}"#.to_vec())),
+            _ => Ok((200, b"OK".to_vec())),
+        }
+    }
+}
+
+#[test]
+fn http_backend() {
+    let posts = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+    let b = MockBackend { posts: posts.clone() };
+    let c = Client::with_url("http://mock/prefix").unwrap().with_backend(b);
+    assert_eq!(c.price(None::<&Address>, 0).unwrap(), Winstons::from(42u32));
+    match c.info() {
+        Err(Error::HttpStatus { status, body, url, .. }) => {
+            assert_eq!((status, body.as_str()), (404, "no /prefix/info"));
+            assert_eq!(url, "http://mock/prefix/info");
+        },
+        r => panic!("unexpected: {:?}", r.map(|_| ())),
+    }
+
+    let tx: Tx = serde_json::from_str(include_str!("fixtures/tx_v2.json")).unwrap();
+    assert_eq!(c.submit(&tx).unwrap(), tx.id);
+    let posts = posts.lock().unwrap();
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0].0, "/prefix/tx");
+    assert_eq!(serde_json::from_slice::<Tx>(&posts[0].1).unwrap(), tx);
+}
+
+#[test]
+fn pss_parameters() {
+    use openssl::{hash::MessageDigest, rsa::Padding, sign::RsaPssSaltlen};
+    let w = Wallet::from_jwk(include_str!("fixtures/wallet.json")).unwrap();
+    let pk = openssl::pkey::PKey::from_rsa(w.owner().pubkey().unwrap()).unwrap();
+    let verify = |msg: &[u8], sig: &[u8], saltlen: RsaPssSaltlen| {
+        let mut v = openssl::sign::Verifier::new(MessageDigest::sha256(), &pk).unwrap();
+        v.set_rsa_padding(Padding::PKCS1_PSS).unwrap();
+        v.set_rsa_mgf1_md(MessageDigest::sha256()).unwrap();
+        v.set_rsa_pss_saltlen(saltlen).unwrap();
+        v.update(msg).unwrap();
+        v.verify(sig).unwrap()
+    };
+
+    let tx: Tx = serde_json::from_str(include_str!("fixtures/tx_v2.json")).unwrap();
+    let txb = TxBuilder::new(tx.anchor.clone()).format(2).target(tx.target().unwrap().clone())
+        .quantity(tx.quantity.clone()).reward_winstons(tx.reward.clone())
+        .data(tx.data.clone()).tags(tx.tags.clone());
+    let msg = txb.signing_data(w.owner()).unwrap();
+    assert!(verify(&msg, tx.signature.as_bytes(), RsaPssSaltlen::MAXIMUM_LENGTH));
+
+    // we sign with the maximum salt length: 512 - 32 - 2 bytes for a 4096 bit key
+    let sig = w.sign_message("hello").unwrap();
+    assert!(verify(b"hello", sig.as_bytes(), RsaPssSaltlen::custom(478)));
+    assert!(!verify(b"hello", sig.as_bytes(), RsaPssSaltlen::DIGEST_LENGTH));
+
+    // and accept the digest length salts produced by WebCrypto
+    let mut s = openssl::sign::Signer::new(MessageDigest::sha256(), w.key()).unwrap();
+    s.set_rsa_padding(Padding::PKCS1_PSS).unwrap();
+    s.set_rsa_pss_saltlen(RsaPssSaltlen::DIGEST_LENGTH).unwrap();
+    s.update(b"hello").unwrap();
+    let sig = Signature::new(s.sign_to_vec().unwrap()).unwrap();
+    assert!(w.owner().verify_message("hello", &sig).unwrap());
+}
+
+#[test]
+fn hash_at_height() {
+    let b1 = "2serU-303rThvozelaLz67ftihCw7cJPAEF40SkPkfyvz92Z5gCKVfhadoVU3ZRg";
+    let c = Client::with_url(serve_routes(vec![
+        ("/block/height/1/indep_hash", Reply::new("200 OK", b1)),
+        ("/block/height/2/indep_hash", Reply::new("404 Not Found", "Not Found")),
+    ])).unwrap();
+    assert_eq!(c.hash_at_height(Height::from(1)).unwrap(), BlockHash::decode(b1).unwrap());
+    assert!(matches!(c.hash_at_height(Height::from(2)), Err(Error::HttpStatus { status: 404, .. })));
+}
+
+#[test]
+fn tx_builder_template() {
+    let w0 = Wallet::from_jwk(include_str!("fixtures/wallet.json")).unwrap();
+    let w1 = Wallet::with_key_size(2048).unwrap();
+    let template = TxBuilder::new(Anchor::Transaction(None))
+        .data_str("hello").reward_winstons(Winstons::from(42u32));
+
+    let tx0 = template.clone().sign(&w0).unwrap();
+    let tx1 = template.clone().sign(&w1).unwrap();
+    let anchor = Anchor::Block(BlockHash::decode("pXJ_5CPNqY7H1UvoIiBKnLUtLpMz9Gdd1k42TIUIRAZcUkr8mT6FSZV7Lg8HpMjh").unwrap());
+    let tx2 = template.anchor(anchor.clone()).sign(&w0).unwrap();
+    assert!(tx0.verify().unwrap() && tx1.verify().unwrap() && tx2.verify().unwrap());
+    assert_eq!(tx0.data, tx1.data);
+    assert_ne!(tx0.owner, tx1.owner);
+    assert_eq!(tx2.anchor, anchor);
+}
+
+#[test]
+fn raw_hash_bytes() {
+    let txh = TxHash::decode("P_UI7i6Cj3s046yuDMDEhvRTpauP3XE5LsVSoOdd3wI").unwrap();
+    let mut bs = [0; 32];
+    bs.copy_from_slice(txh.as_bytes());
+    assert_eq!(TxHash::from_bytes(bs), txh);
+
+    let bh = BlockHash::decode("pXJ_5CPNqY7H1UvoIiBKnLUtLpMz9Gdd1k42TIUIRAZcUkr8mT6FSZV7Lg8HpMjh").unwrap();
+    let mut bs = [0; 48];
+    bs.copy_from_slice(bh.as_bytes());
+    assert_eq!(BlockHash::from_bytes(bs), bh);
+
+    let a = Address::from_bytes([7; 32]);
+    assert_eq!(a.as_bytes(), &[7; 32]);
+    assert_eq!(Address::new([7; 32]).unwrap(), a);
+
+    let mut m = std::collections::HashMap::new();
+    m.insert(a.clone(), 1);
+    assert_eq!(m.get(&a), Some(&1));
+}
+
+#[test]
+fn wallet_from_file() {
+    let fixture = |f: &str| std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(f);
+    let a = Wallet::from_file(fixture("wallet.json")).unwrap().address().clone();
+    assert_eq!(Wallet::from_file(fixture("wallet.pem")).unwrap().address(), &a);
+    assert!(matches!(Wallet::from_file(fixture("wallet.pub.pem")), Err(Error::OpensslError(_))));
+    match Wallet::from_file(fixture("tx_v2.json")) {
+        Err(Error::InvalidValue { msg, .. }) => assert_eq!(msg, "unrecognized format (tried JWK and PEM)"),
+        r => panic!("unexpected: {:?}", r),
+    }
+    assert!(matches!(Wallet::from_file(fixture("missing.json")), Err(Error::IoError(_))));
+}
+
+#[test]
+fn tx_offset() {
+    let txh = TxHash::decode("P_UI7i6Cj3s046yuDMDEhvRTpauP3XE5LsVSoOdd3wI").unwrap();
+    let url = serve(vec![
+        ("200 OK", r#"{"size":"262144","offset":"10485759"}"#),
+        ("404 Not Found", "Not Found"),
+    ]);
+    let c = Client::with_url(url).unwrap();
+    assert_eq!(c.tx_offset(&txh).unwrap(), TxOffset { size: 262144, offset: 10485759 });
+    assert!(matches!(c.tx_offset(&txh), Err(Error::ValueNotPresent { .. })));
+}
+
+#[test]
+fn progress() {
+    use std::io::Read;
+    use std::sync::{Arc, Mutex};
+    let seen = Arc::new(Mutex::new(vec![]));
+    let s = seen.clone();
+    let c = Client::with_url(serve_once("200 OK", "hello")).unwrap()
+        .with_progress(move |done, total| s.lock().unwrap().push((done, total)));
+    let txh = TxHash::decode("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA").unwrap();
+    let mut buf = Vec::new();
+    c.data_stream(&txh).unwrap().read_to_end(&mut buf).unwrap();
+    assert_eq!(buf, b"hello");
+    assert_eq!(seen.lock().unwrap().last(), Some(&(5, 5)));
+
+    let w = Wallet::from_jwk(include_str!("fixtures/wallet.json")).unwrap();
+    let data = vec![7u8; 600 * 1024];
+    let tx = TxBuilder::new(Anchor::Transaction(None)).format(2)
+        .data(Data::from(data.clone())).reward_winstons(Winstons::from(42u32)).sign(&w).unwrap();
+    let n = DataTree::from_bytes(&data).len();
+    let posts = Arc::new(Mutex::new(vec![]));
+    let seen = Arc::new(Mutex::new(vec![]));
+    let s = seen.clone();
+    let c = Client::with_url("http://mock").unwrap()
+        .with_backend(MockBackend { posts: posts.clone() })
+        .with_progress(move |done, total| s.lock().unwrap().push((done, total)));
+    c.upload_chunks(&tx).unwrap();
+    assert_eq!(posts.lock().unwrap().len(), n);
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), n);
+    assert_eq!(seen.last(), Some(&(data.len() as u64, data.len() as u64)));
+    assert!(seen.windows(2).all(|w| w[0].0 < w[1].0));
+}
+
+#[test]
+fn quantity_without_target() {
+    let w = Wallet::from_jwk(include_str!("fixtures/wallet.json")).unwrap();
+    let txb = || TxBuilder::new(Anchor::Transaction(None)).reward_winstons(Winstons::from(42u32));
+
+    assert!(matches!(txb().quantity(Winstons::from(1u32)).sign(&w), Err(Error::InvalidValue { .. })));
+    assert!(matches!(txb().sign(&w).map(|tx| tx.quantity.is_zero()), Ok(true)));
+    assert!(txb().quantity(Winstons::from(1u32)).allow_burn().sign(&w).unwrap().verify().unwrap());
+    assert!(txb().allow_burn().quantity(Winstons::from(1u32)).sign(&w).unwrap().verify().unwrap());
+    assert!(txb().target(fresh::address()).data_str("hello").sign(&w).unwrap().verify().unwrap());
+    assert!(txb().target(fresh::address()).quantity(Winstons::from(1u32)).sign(&w).unwrap().verify().unwrap());
+}
+
+#[test]
+fn http_status_headers() {
+    let url = serve_routes(vec![("/peers", Reply::new("503 Service Unavailable", "busy").header("x-trace-id", "abc123"))]);
+
+    let e = Client::with_url(&url).unwrap().peers().unwrap_err();
+    match &e {
+        Error::HttpStatus { status, body, url: u, headers } => {
+            assert_eq!((*status, body.as_str()), (503, "busy"));
+            assert_eq!(u, &format!("{}/peers", url));
+            assert_eq!(headers.get("x-trace-id").unwrap(), "abc123");
+        },
+        e => panic!("unexpected: {:?}", e),
+    }
+    assert_eq!(e.to_string(), format!("http status 503 from {}/peers: busy", url));
+}
+
+#[test]
+fn http_status_headers_on_post() {
+    let url = serve_routes(vec![("/tx", Reply::new("400 Bad Request", "invalid").header("x-trace-id", "def456"))]);
+    let tx: Tx = serde_json::from_str(include_str!("fixtures/tx_v2.json")).unwrap();
+    match Client::with_url(&url).unwrap().submit(&tx) {
+        Err(Error::HttpStatus { status, body, url: u, headers }) => {
+            assert_eq!((status, body.as_str()), (400, "invalid"));
+            assert_eq!(u, format!("{}/tx", url));
+            assert_eq!(headers.get("x-trace-id").unwrap(), "def456");
+        },
+        r => panic!("unexpected: {:?}", r),
+    }
+}
+
+#[test]
+fn preview_id() {
+    let w = Wallet::from_jwk(include_str!("fixtures/wallet.json")).unwrap();
+    let txb = TxBuilder::new(Anchor::Transaction(None)).data_str("hello");
+    assert!(matches!(txb.preview_id(&w), Err(Error::ValueNotPresent { .. })));
+
+    let txb = txb.reward_winstons(Winstons::from(42u32));
+    let (id, sig) = txb.preview_id(&w).unwrap();
+    let tx = txb.with_signature(w.owner().clone(), sig).unwrap();
+    assert_eq!(tx.id, id);
+    assert!(tx.verify().unwrap());
+}
+
+#[test]
+fn binary_tag_constructors() {
+    let (n, v) = (Name::from_bytes(&[0xff, 0x00, b'k']), Value::from_bytes(&[0xc3, 0x28]));
+    assert_eq!(n.as_bytes(), &[0xff, 0x00, b'k']);
+    assert_eq!(Name::from_bytes(b"App-Name"), Name::from("App-Name"));
+    assert_eq!(Value::from_bytes(b"arweaver"), Value::from("arweaver"));
+
+    let w = Wallet::from_jwk(include_str!("fixtures/wallet.json")).unwrap();
+    let tx = TxBuilder::new(Anchor::Transaction(None))
+        .add_tag((n.clone(), v.clone())).reward_winstons(Winstons::from(42u32))
+        .sign(&w).unwrap();
+    assert!(tx.verify().unwrap());
+
+    let tx: Tx = serde_json::from_str(&serde_json::to_string(&tx).unwrap()).unwrap();
+    let t = tx.tags.iter().next().unwrap();
+    assert_eq!((t.name(), t.value()), (&n, &v));
+}
+
+#[test]
+fn query_by_owner_and_recipient() {
+    let posts = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+    let c = Client::with_url("http://mock/prefix").unwrap().with_backend(MockBackend { posts: posts.clone() });
+    let a = fresh::address();
+    let empty = TxPage { txs: vec![], cursor: None };
+    assert_eq!(c.query_by_owner(&a, None).unwrap(), empty);
+    assert_eq!(c.query_by_recipient(&a, Some("c0".to_string())).unwrap(), empty);
+
+    let vars = posts.lock().unwrap().iter()
+        .map(|(_, b)| serde_json::from_slice::<serde_json::Value>(b).unwrap()["variables"].take())
+        .collect::<Vec<_>>();
+    assert_eq!(vars, vec![
+        serde_json::json!({ "owners": [a.encode()] }),
+        serde_json::json!({ "recipients": [a.encode()], "after": "c0" }),
+    ]);
+}
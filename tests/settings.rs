@@ -6,12 +6,16 @@ pub fn recent_block_height() -> Height {
 }
 
 pub fn recent_block_timestamp() -> DateTime<Utc> {
-    Utc.ymd(2019, 11, 7).and_hms(15, 03, 24)
+    Utc.with_ymd_and_hms(2019, 11, 7, 15, 3, 24).unwrap()
+}
+
+pub fn format_2_0_block_height() -> Height {
+    Height::from(500000)
 }
 
 pub fn block_with_transactions() -> (BlockHash, DateTime<Utc>)  {
     let bh = BlockHash::decode("TQpzWTuYMv82YPLEeaAKJawJlknA5cDcesHCGVvZFzSFrpfWZxc-tOmLU-lx1B4v").unwrap();
-    let ts = Utc.ymd(2019, 11, 7).and_hms(11, 59, 38);
+    let ts = Utc.with_ymd_and_hms(2019, 11, 7, 11, 59, 38).unwrap();
     (bh, ts)
 }
 
@@ -0,0 +1,31 @@
+#![allow(dead_code)]
+
+use openssl::{bn::BigNum, hash::MessageDigest, pkey::PKey, rsa::{Padding, Rsa}};
+use openssl::sign::{RsaPssSaltlen, Verifier};
+
+fn binary(v: &serde_json::Value) -> Vec<u8> {
+    base64::decode_config(v.as_str().unwrap(), base64::URL_SAFE_NO_PAD).unwrap()
+}
+
+// https://github.com/ArweaveTeam/arweave/blob/d882d8a5880b765cd9a65928eaf7c04ea6aedfea/src/ar_tx.erl#L54
+pub fn preimage(tx: &serde_json::Value) -> Vec<u8> {
+    let mut bs = Vec::new();
+    for f in &["owner", "target", "data"] { bs.extend(binary(&tx[f])); }
+    for f in &["quantity", "reward"] { bs.extend(tx[f].as_str().unwrap().as_bytes()); }
+    bs.extend(binary(&tx["last_tx"]));
+    for t in tx["tags"].as_array().unwrap() {
+        bs.extend(binary(&t["name"]));
+        bs.extend(binary(&t["value"]));
+    }
+    bs
+}
+
+pub fn verify(tx: &serde_json::Value, msg: &[u8]) -> bool {
+    let n = BigNum::from_slice(&binary(&tx["owner"])).unwrap();
+    let pk = PKey::from_rsa(Rsa::from_public_components(n, BigNum::from_u32(65537).unwrap()).unwrap()).unwrap();
+    let mut v = Verifier::new(MessageDigest::sha256(), &pk).unwrap();
+    v.set_rsa_padding(Padding::PKCS1_PSS).unwrap();
+    v.set_rsa_pss_saltlen(RsaPssSaltlen::custom(-2)).unwrap();
+    v.update(msg).unwrap();
+    v.verify(&binary(&tx["signature"])).unwrap()
+}